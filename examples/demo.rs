@@ -0,0 +1,1089 @@
+use {
+    linkedhashmap::{AccessOrderMap, Entry, LinkedHashMap, LinkedHashMultimap, LinkedHashSet, LruCache, ShardedLinkedHashMap, SyncLinkedHashMap},
+    std::{
+        rc::Rc,
+        sync::{Arc, Mutex},
+        collections::{hash_map::DefaultHasher, HashMap},
+        hash::{BuildHasher, BuildHasherDefault},
+        time::{Duration, Instant},
+    },
+};
+
+// A map as a field of a struct deriving Default
+#[derive(Default)]
+struct Session {
+    history: LinkedHashMap<&'static str, u32>,
+}
+
+// Quick tests
+fn main() {
+    let mut linked_hash_map = LinkedHashMap::new();
+
+    // Insertion
+    linked_hash_map.insert("First", 5);
+    linked_hash_map.insert("Second", 8);
+    linked_hash_map.insert("Third", 9);
+    linked_hash_map.insert("Fourth", 11);
+    linked_hash_map.insert("Fifth", 15);
+    linked_hash_map.insert("Sixth", 20);
+
+    // Iteration in order of insertion
+    for (key, val) in linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Removal
+    linked_hash_map.remove(&"Third");
+    linked_hash_map.remove(&"Fourth");
+
+    // Iteration in order of insertion
+    // after removing intermediate elements
+    for (key, val) in linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Get a random element through the HashMap structure
+    // which is best case O(1)
+    println!("{}\n", linked_hash_map.get(&"Fifth").unwrap());
+
+    // Insert an existing key, which replaces the value in place
+    println!("{:?}", linked_hash_map.insert("Second", 80));
+
+    for (key, val) in linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Mutate an element in place, keeping its position
+    *linked_hash_map.get_mut(&"Fifth").unwrap() += 1;
+    println!("{}\n", linked_hash_map.get(&"Fifth").unwrap());
+
+    // Check for a key without retrieving its value
+    println!("{} {}\n", linked_hash_map.contains_key(&"Fifth"), linked_hash_map.contains_key(&"Third"));
+
+    // Get an element only if its value passes a predicate
+    println!("{:?}", linked_hash_map.get_if(&"Fifth", |val| *val > 10));
+    println!("{:?}\n", linked_hash_map.get_if(&"Fifth", |val| *val > 20));
+
+    // Remove all elements
+    for (i, _) in linked_hash_map.drain().enumerate() {
+        println!("Removing index: {}", i);
+    }
+
+    println!();
+
+    // Count of elements, which are now all removed
+    println!("{} {}\n", linked_hash_map.len(), linked_hash_map.is_empty());
+
+    // Try to remove some non existent element
+    linked_hash_map.remove(&"Garbage");
+
+    // Print remaining elements
+    // (None)
+    for (_, val) in linked_hash_map.iter() {
+        println!("{}", val);
+    }
+
+    println!();
+
+    // Move every even value to the back, keeping relative order on both sides
+    let mut linked_hash_map = LinkedHashMap::new();
+
+    for (key, val) in [("One", 1), ("Two", 2), ("Three", 3), ("Four", 4), ("Five", 5), ("Six", 6)].iter() {
+        linked_hash_map.insert(*key, *val);
+    }
+
+    linked_hash_map.move_matching_to_back(|_, val| val % 2 == 0);
+
+    for (key, val) in linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Running totals over the values in order of insertion
+    for (key, total) in linked_hash_map.scan_values(0, |acc, val| acc + val) {
+        println!("{}: {}", key, total);
+    }
+
+    println!();
+
+    // Nearest value to a target, where the doubled values of "Three" and "Four"
+    // tie around 7 and the earlier "Three" wins
+    println!("{:?}", linked_hash_map.closest_by_value(11, |val| *val as i64 * 2));
+    println!("{:?}", linked_hash_map.closest_by_value(7, |val| *val as i64 * 2));
+
+    println!();
+
+    // Swap the contents of two maps
+    let mut other_linked_hash_map = LinkedHashMap::new();
+    other_linked_hash_map.insert("Seven", 7);
+    other_linked_hash_map.insert("Eight", 8);
+
+    linked_hash_map.swap(&mut other_linked_hash_map);
+
+    for (key, val) in linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    for (key, val) in other_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Take the contents, leaving an empty map that can still be used
+    let taken_linked_hash_map = linked_hash_map.take();
+    linked_hash_map.insert("Nine", 9);
+
+    for (key, val) in taken_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    for (key, val) in linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Count values grouped by parity
+    let parity_counts = other_linked_hash_map.count_by(|val| val % 2 == 0);
+    println!("Even: {}, Odd: {}", parity_counts[&true], parity_counts[&false]);
+
+    println!();
+
+    // Purge timestamped entries older than a cutoff
+    let start = Instant::now();
+    let mut timestamped_linked_hash_map = LinkedHashMap::new();
+
+    for (i, key) in ["Ten", "Eleven", "Twelve", "Thirteen"].iter().enumerate() {
+        timestamped_linked_hash_map.insert_at(*key, i, start + Duration::from_secs(i as u64));
+    }
+
+    timestamped_linked_hash_map.purge_older_than(start + Duration::from_secs(2));
+
+    for (key, (_, val)) in timestamped_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Median values, taking the lower median for an even number of entries
+    println!("{:?}", other_linked_hash_map.median_by_value());
+    other_linked_hash_map.remove(&"Six");
+    println!("{:?}", other_linked_hash_map.median_by_value());
+
+    println!();
+
+    // Shrink to two entries, evicting the oldest ones
+    for (key, val) in other_linked_hash_map.shrink_to_len(2) {
+        println!("Evicted {}: {}", key, val);
+    }
+
+    for (key, val) in other_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Keys inserted since a checkpoint, even after the checkpointed entry is removed
+    let checkpoint = other_linked_hash_map.checkpoint();
+    other_linked_hash_map.insert("Fourteen", 14);
+    other_linked_hash_map.insert("Fifteen", 15);
+    other_linked_hash_map.remove(&"Four");
+
+    println!("{:?}", other_linked_hash_map.keys_since(&checkpoint));
+
+    println!();
+
+    // Drain the leading run of values below a threshold
+    let mut sorted_linked_hash_map = LinkedHashMap::new();
+
+    for (key, val) in [("A", 1), ("B", 3), ("C", 5), ("D", 7), ("E", 9)].iter() {
+        sorted_linked_hash_map.insert(*key, *val);
+    }
+
+    println!("{:?}", sorted_linked_hash_map.drain_while(|_, val| *val < 4));
+
+    for (key, val) in sorted_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Check whether the order of insertion is ascending key order
+    println!("{}", sorted_linked_hash_map.is_key_sorted());
+    sorted_linked_hash_map.insert("B", 3);
+    println!("{}", sorted_linked_hash_map.is_key_sorted());
+
+    println!();
+
+    // Find the first value over a threshold along with its position
+    println!("{:?}", sorted_linked_hash_map.find_with_index(|val| *val > 6));
+
+    println!();
+
+    // Override some of the values, leaving every entry in its position
+    let mut overrides = HashMap::new();
+    overrides.insert("D", 70);
+    overrides.insert("B", 30);
+    overrides.insert("Z", 0);
+
+    sorted_linked_hash_map.apply_overrides(&overrides);
+
+    for (key, val) in sorted_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Shard seven entries into three maps
+    let mut shardable_linked_hash_map = LinkedHashMap::new();
+
+    for i in 0..7 {
+        shardable_linked_hash_map.insert(i, i * 10);
+    }
+
+    for shard in shardable_linked_hash_map.shard(3) {
+        println!("{:?}", shard.iter().collect::<Vec<_>>());
+    }
+
+    println!();
+
+    // Interleave a three and a two element map
+    let mut left_linked_hash_map = LinkedHashMap::new();
+    left_linked_hash_map.insert("L1", 1);
+    left_linked_hash_map.insert("L2", 2);
+    left_linked_hash_map.insert("L3", 3);
+
+    let mut right_linked_hash_map = LinkedHashMap::new();
+    right_linked_hash_map.insert("R1", 1);
+    right_linked_hash_map.insert("R2", 2);
+
+    for (key, val) in left_linked_hash_map.interleave(right_linked_hash_map).iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Position shift of a key moved from index 1 to index 3
+    let mut before_linked_hash_map = LinkedHashMap::new();
+    let mut after_linked_hash_map = LinkedHashMap::new();
+
+    for key in ["P0", "P1", "P2", "P3"].iter() {
+        before_linked_hash_map.insert(*key, 0);
+    }
+
+    for key in ["P0", "P2", "P3", "P1"].iter() {
+        after_linked_hash_map.insert(*key, 0);
+    }
+
+    println!("{:?}", after_linked_hash_map.position_shift(&before_linked_hash_map, &"P1"));
+
+    println!();
+
+    // Pop the leading expired entries, counting them
+    let mut expiring_linked_hash_map = LinkedHashMap::new();
+
+    for (key, expired) in [("X1", true), ("X2", true), ("X3", false), ("X4", true)].iter() {
+        expiring_linked_hash_map.insert(*key, *expired);
+    }
+
+    println!("{}", expiring_linked_hash_map.pop_front_while(|_, expired| *expired));
+
+    for (key, val) in expiring_linked_hash_map.iter() {
+        println!("{}: {}", key, val);
+    }
+
+    println!();
+
+    // Read through a view which offers no way to mutate the map
+    let readonly_view = expiring_linked_hash_map.as_readonly();
+    println!("{} {} {}", readonly_view.len(), readonly_view.is_empty(), readonly_view.contains_key(&"X3"));
+    println!("{:?} {:?}", readonly_view.get(&"X4"), readonly_view.iter().collect::<Vec<_>>());
+
+    println!();
+
+    // Parse every value, stopping at the first one that fails
+    let mut unparsed_linked_hash_map = LinkedHashMap::new();
+    unparsed_linked_hash_map.insert("Port", "8080");
+    unparsed_linked_hash_map.insert("Timeout", "30");
+
+    let mut invalid_linked_hash_map = LinkedHashMap::new();
+    invalid_linked_hash_map.insert("Port", "8080");
+    invalid_linked_hash_map.insert("Timeout", "soon");
+
+    match unparsed_linked_hash_map.try_map_values(|_, val| val.parse::<u32>()) {
+        Ok(parsed) => println!("{:?}", parsed.iter().collect::<Vec<_>>()),
+        Err(err) => println!("{}", err),
+    }
+
+    match invalid_linked_hash_map.try_map_values(|_, val| val.parse::<u32>()) {
+        Ok(parsed) => println!("{:?}", parsed.iter().collect::<Vec<_>>()),
+        Err(err) => println!("{}", err),
+    }
+
+    println!();
+
+    // Inversions against the reversed order, which is the maximum of n * (n - 1) / 2
+    let reversed_keys = ["P3", "P2", "P1", "P0"];
+    println!("{}", before_linked_hash_map.inversions_vs(&reversed_keys));
+
+    println!();
+
+    // Snapshot of the entries as ordered pairs
+    println!("{:?}", before_linked_hash_map.to_pairs());
+
+    println!();
+
+    // Where the small values cluster across four ranges of positions
+    println!("{:?}", shardable_linked_hash_map.index_histogram(4, |_, val| *val < 40));
+
+    println!();
+
+    // Map keys to their lengths, where "Be" and "Go" collide and "Be" is kept
+    let mut named_linked_hash_map = LinkedHashMap::new();
+    named_linked_hash_map.insert("Alpha", 1);
+    named_linked_hash_map.insert("Be", 2);
+    named_linked_hash_map.insert("Go", 3);
+    named_linked_hash_map.insert("Gamma!", 4);
+
+    println!("{:?}", named_linked_hash_map.map_keys(|key| key.len()).to_pairs());
+
+    println!();
+
+    // Windows around a central key, a key near the start, and an absent key
+    println!("{:?}", shardable_linked_hash_map.window_around(&3, 2));
+    println!("{:?}", shardable_linked_hash_map.window_around(&1, 2));
+    println!("{:?}", shardable_linked_hash_map.window_around(&10, 2));
+
+    println!();
+
+    // Count words with the Entry API, where new words go to the back
+    let mut word_counts = LinkedHashMap::new();
+
+    for word in "the quick fox and the lazy fox and the dog".split(' ') {
+        *word_counts.entry(word).or_insert(0) += 1;
+    }
+
+    word_counts.entry("dog").and_modify(|count| *count *= 10).or_default();
+    word_counts.entry("cat").and_modify(|count| *count *= 10).or_default();
+
+    println!("{:?}", word_counts.to_pairs());
+
+    println!();
+
+    // Clear every entry, after which the map can be reused
+    word_counts.clear();
+    word_counts.insert("again", 1);
+
+    println!("{:?}", word_counts.to_pairs());
+
+    println!();
+
+    // Removed and dropped entries are freed, leaving only the local references
+    let shared_key = Rc::new("Shared");
+    let mut counted_linked_hash_map = LinkedHashMap::new();
+    counted_linked_hash_map.insert(Rc::clone(&shared_key), 1);
+    counted_linked_hash_map.insert(Rc::new("Other"), 2);
+
+    println!("{}", Rc::strong_count(&shared_key));
+    counted_linked_hash_map.remove(&shared_key);
+    println!("{}", Rc::strong_count(&shared_key));
+    counted_linked_hash_map.insert(Rc::clone(&shared_key), 1);
+    drop(counted_linked_hash_map);
+    println!("{}", Rc::strong_count(&shared_key));
+
+    println!();
+
+    // Drop a long chain without recursing through every entry
+    let mut large_linked_hash_map = LinkedHashMap::new();
+
+    for i in 0..200_000 {
+        large_linked_hash_map.insert(i, i);
+    }
+
+    drop(large_linked_hash_map);
+    println!("Dropped");
+
+    println!();
+
+    // Iterate over borrowed keys and values, without cloning out the strings
+    let mut blob_linked_hash_map = LinkedHashMap::new();
+    blob_linked_hash_map.insert(String::from("First"), "a".repeat(8));
+    blob_linked_hash_map.insert(String::from("Second"), "b".repeat(16));
+
+    for (key, val) in blob_linked_hash_map.iter() {
+        println!("{}: {}", key, val.len());
+    }
+
+    println!();
+
+    // Update every value in place, in order of insertion
+    let mut scored_linked_hash_map = LinkedHashMap::new();
+    scored_linked_hash_map.insert("Old", 40);
+    scored_linked_hash_map.insert("Recent", 100);
+    scored_linked_hash_map.insert("New", 10);
+
+    for (_, score) in scored_linked_hash_map.iter_mut() {
+        *score /= 2;
+    }
+
+    println!("{:?}", scored_linked_hash_map.to_pairs());
+
+    println!();
+
+    // Loop over the map by reference, by mutable reference and by value
+    for (_, score) in &mut scored_linked_hash_map {
+        *score += 1;
+    }
+
+    for (name, score) in &scored_linked_hash_map {
+        println!("{}: {}", name, score);
+    }
+
+    let names: Vec<String> = blob_linked_hash_map.into_iter().map(|(key, _)| key).collect();
+    println!("{:?}", names);
+
+    println!();
+
+    // Iterate over only the keys or only the values
+    for score in scored_linked_hash_map.values_mut() {
+        *score *= 10;
+    }
+
+    println!("{:?}", scored_linked_hash_map.keys().collect::<Vec<_>>());
+    println!("{}", scored_linked_hash_map.values().sum::<i32>());
+
+    println!();
+
+    // Move the keys or the values out of the map
+    let mut staging_linked_hash_map = LinkedHashMap::new();
+    staging_linked_hash_map.insert(String::from("Third"), 3);
+    staging_linked_hash_map.insert(String::from("First"), 1);
+
+    let staged_values: Vec<i32> = scored_linked_hash_map.into_values().collect();
+    let staged_keys: Vec<String> = staging_linked_hash_map.into_keys().collect();
+    println!("{:?} {:?}", staged_keys, staged_values);
+
+    println!();
+
+    // Most recently inserted first
+    let mut recent_linked_hash_map = LinkedHashMap::new();
+    recent_linked_hash_map.insert("Monday", 1);
+    recent_linked_hash_map.insert("Tuesday", 2);
+    recent_linked_hash_map.insert("Wednesday", 3);
+
+    println!("{:?}", recent_linked_hash_map.iter().rev().collect::<Vec<_>>());
+    println!("{:?}", recent_linked_hash_map.keys().rev().collect::<Vec<_>>());
+
+    println!();
+
+    // Iterators know exactly how many entries they have left
+    let mut days = recent_linked_hash_map.iter();
+    days.next();
+    println!("{}", days.len());
+    println!("{:?}", recent_linked_hash_map.values().zip(days).collect::<Vec<_>>());
+
+    println!();
+
+    // Collect pairs into a map, where a repeated key keeps its first position
+    let collected_linked_hash_map: LinkedHashMap<_, _> = vec![("b", 1), ("a", 2), ("b", 3)]
+        .into_iter()
+        .collect();
+
+    println!("{:?}", collected_linked_hash_map.to_pairs());
+
+    println!();
+
+    // Extend a map with owned or borrowed pairs
+    let mut extended_linked_hash_map = LinkedHashMap::new();
+    extended_linked_hash_map.insert("a", 0);
+    extended_linked_hash_map.extend(vec![("c", 4), ("a", 5)]);
+    extended_linked_hash_map.extend(&collected_linked_hash_map);
+
+    println!("{:?}", extended_linked_hash_map.to_pairs());
+
+    println!();
+
+    // Print the map itself, in order of insertion
+    println!("{:?}", extended_linked_hash_map);
+    println!("{:?}", LinkedHashMap::<i32, i32>::new());
+
+    println!();
+
+    // Changing a clone leaves the original untouched
+    let mut cloned_linked_hash_map = extended_linked_hash_map.clone();
+    cloned_linked_hash_map.remove(&"a");
+    cloned_linked_hash_map.insert("d", 6);
+
+    println!("{:?}", extended_linked_hash_map);
+    println!("{:?}", cloned_linked_hash_map);
+
+    println!();
+
+    // Maps are equal when their pairs are equal and in the same order
+    let forward_linked_hash_map: LinkedHashMap<_, _> = vec![(1, 'a'), (2, 'b')].into_iter().collect();
+    let backward_linked_hash_map: LinkedHashMap<_, _> = vec![(2, 'b'), (1, 'a')].into_iter().collect();
+
+    println!("{}", forward_linked_hash_map == forward_linked_hash_map.clone());
+    println!("{}", forward_linked_hash_map == backward_linked_hash_map);
+    println!("{}", forward_linked_hash_map.eq_ignore_order(&backward_linked_hash_map));
+
+    println!();
+
+    // Start from an empty map built by Default
+    let mut session = Session::default();
+    session.history.insert("Home", 1);
+    session.history.insert("Settings", 2);
+
+    println!("{:?}", session.history);
+
+    println!();
+
+    // Index the map by key
+    session.history[&"Home"] += 10;
+    println!("{} {}", session.history[&"Home"], session.history[&"Settings"]);
+
+    println!();
+
+    // Use whole maps as keys of another map
+    let mut memoized: HashMap<LinkedHashMap<i32, char>, &str> = HashMap::new();
+    memoized.insert(forward_linked_hash_map.clone(), "Forward");
+    memoized.insert(backward_linked_hash_map.clone(), "Backward");
+
+    println!("{:?}", memoized.get(&forward_linked_hash_map));
+    println!("{}", memoized.len());
+
+    println!();
+
+    // Look at the oldest and newest entries
+    println!("{:?} {:?}", session.history.front(), session.history.back());
+    println!("{:?}", LinkedHashMap::<i32, i32>::new().front());
+
+    println!();
+
+    // Use the map as a queue from either end
+    let mut queued_linked_hash_map: LinkedHashMap<_, _> = (1..=4).map(|i| (i, i * i)).collect();
+
+    println!("{:?}", queued_linked_hash_map.pop_front());
+    println!("{:?}", queued_linked_hash_map.pop_back());
+    println!("{:?}", queued_linked_hash_map);
+
+    println!();
+
+    // Move entries to either end without reinserting them
+    let mut ordered_linked_hash_map: LinkedHashMap<_, _> = "abcd".chars().zip(1..).collect();
+    ordered_linked_hash_map.move_to_back(&'a');
+    ordered_linked_hash_map.move_to_front(&'c');
+
+    println!("{:?}", ordered_linked_hash_map);
+    println!("{}", ordered_linked_hash_map.move_to_back(&'z'));
+
+    println!();
+
+    // Keep entries in order of access, the least recently used first
+    let mut accessed_map = AccessOrderMap::new();
+    accessed_map.insert("Home", 1);
+    accessed_map.insert("Settings", 2);
+    accessed_map.insert("Profile", 3);
+
+    accessed_map.get(&"Home");
+    if let Some(visits) = accessed_map.get_mut(&"Settings") {
+        *visits += 1;
+    }
+    accessed_map.peek(&"Profile");
+
+    println!("{:?}", accessed_map.as_map());
+    println!("{:?}", accessed_map.pop_front());
+
+    println!();
+
+    // Bounded cache evicting the least recently used entry
+    let mut lru_cache = LruCache::with_capacity(2);
+    lru_cache.put(1, "One");
+    lru_cache.put(2, "Two");
+    lru_cache.get(&1);
+    lru_cache.put(3, "Three");
+
+    println!("{:?}", lru_cache.iter().collect::<Vec<_>>());
+    println!("{:?} {}", lru_cache.put(1, "Uno"), lru_cache.len());
+
+    // Look at entries without refreshing them
+    if let Some(val) = lru_cache.peek_mut(&3) {
+        *val = "Tres";
+    }
+    println!("{:?} {:?}", lru_cache.peek_lru(), lru_cache.peek(&3));
+
+    println!();
+
+    // Allocate room for the entries ahead of loading them
+    let mut preallocated_linked_hash_map = LinkedHashMap::with_capacity(1000);
+    let reserved = preallocated_linked_hash_map.capacity();
+
+    for i in 0..1000 {
+        preallocated_linked_hash_map.insert(i, i);
+    }
+
+    println!("{}", preallocated_linked_hash_map.capacity() == reserved);
+    println!("{}", preallocated_linked_hash_map.try_reserve(usize::MAX).is_err());
+
+    preallocated_linked_hash_map.shrink_to_len(10);
+    preallocated_linked_hash_map.shrink_to_fit();
+    preallocated_linked_hash_map.reserve(10);
+    println!("{}", preallocated_linked_hash_map.capacity() < reserved);
+
+    println!();
+
+    // Plug in a hasher of our own choosing
+    let mut hashed_linked_hash_map: LinkedHashMap<_, _, BuildHasherDefault<DefaultHasher>> =
+        LinkedHashMap::with_hasher(BuildHasherDefault::default());
+    hashed_linked_hash_map.insert("Deterministic", 1);
+    hashed_linked_hash_map.extend(vec![("Hashing", 2), ("Order", 3)]);
+
+    println!("{:?}", hashed_linked_hash_map);
+    println!("{}", hashed_linked_hash_map.clone() == hashed_linked_hash_map);
+
+    println!();
+
+    // Hand the map over to another thread
+    let mut threaded_linked_hash_map = LinkedHashMap::new();
+    threaded_linked_hash_map.insert("Main", 1);
+
+    let worker = std::thread::spawn(move || {
+        threaded_linked_hash_map.insert("Worker", 2);
+        threaded_linked_hash_map
+    });
+
+    println!("{:?}", worker.join().unwrap());
+
+    println!();
+
+    // Share one map between threads, each inserting its own key
+    let shared_linked_hash_map = Arc::new(SyncLinkedHashMap::new());
+    shared_linked_hash_map.insert(0, "Main");
+
+    let workers: Vec<_> = (1..4)
+        .map(|i| {
+            let shared_linked_hash_map = Arc::clone(&shared_linked_hash_map);
+            std::thread::spawn(move || {
+                shared_linked_hash_map.insert(i, "Worker");
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    println!("{} {:?}", shared_linked_hash_map.len(), shared_linked_hash_map.get(&0));
+
+    println!();
+
+    // Spread the keys over independently locked shards
+    let sharded_linked_hash_map = Arc::new(ShardedLinkedHashMap::with_shards(4));
+
+    let workers: Vec<_> = (0..4)
+        .map(|i| {
+            let sharded_linked_hash_map = Arc::clone(&sharded_linked_hash_map);
+            std::thread::spawn(move || {
+                for key in i * 10..i * 10 + 10 {
+                    sharded_linked_hash_map.insert(key, key * key);
+                }
+            })
+        })
+        .collect();
+
+    for worker in workers {
+        worker.join().unwrap();
+    }
+
+    sharded_linked_hash_map.pop_front();
+    println!("{} {:?}", sharded_linked_hash_map.len(), sharded_linked_hash_map.get(&7));
+
+    println!();
+
+    // Recover the stored key instance of an equal key
+    let mut interned_linked_hash_map = LinkedHashMap::new();
+    let interned = Rc::new("Interned".to_string());
+    interned_linked_hash_map.insert(Rc::clone(&interned), 1);
+
+    let lookup = Rc::new("Interned".to_string());
+    if let Some((key, val)) = interned_linked_hash_map.get_key_value(&lookup) {
+        println!("{} {} {}", key, val, Rc::ptr_eq(key, &interned));
+    }
+
+    let (removed_key, _) = interned_linked_hash_map.remove_entry(&lookup).unwrap();
+    println!("{}", Rc::ptr_eq(&removed_key, &interned));
+
+    println!();
+
+    // Sort the entries in place for deterministic output
+    let mut unsorted_linked_hash_map: LinkedHashMap<_, _> =
+        vec![("Pear", 3), ("Apple", 7), ("Fig", 3), ("Banana", 1)].into_iter().collect();
+    unsorted_linked_hash_map.sort_keys();
+    println!("{:?}", unsorted_linked_hash_map);
+
+    unsorted_linked_hash_map.sort_by(|(_, a), (_, b)| b.cmp(a));
+    println!("{:?}", unsorted_linked_hash_map);
+
+    println!();
+
+    // Place new entries next to an existing key
+    let mut document_linked_hash_map: LinkedHashMap<_, _> = vec![("Title", 1), ("Footer", 3)].into_iter().collect();
+    document_linked_hash_map.insert_before(&"Footer", "Body", 2);
+    document_linked_hash_map.insert_after(&"Footer", "Signature", 4);
+    document_linked_hash_map.insert_before(&"Title", "Footer", 0);
+    println!("{:?}", document_linked_hash_map);
+
+    println!();
+
+    // Look entries up by their position, as when paginating a table
+    let mut paged_linked_hash_map: LinkedHashMap<_, _> = (0..100).map(|i| (format!("Row {}", i), i)).collect();
+    paged_linked_hash_map.remove(&"Row 3".to_string());
+
+    println!("{:?}", paged_linked_hash_map.get_index(10));
+    println!("{:?}", paged_linked_hash_map.get_index_of(&"Row 50".to_string()));
+
+    // A whole page at once, without collecting the map first
+    println!("{:?}", paged_linked_hash_map.iter_range(20..24).map(|(_, val)| val).collect::<Vec<_>>());
+    println!("{:?}", paged_linked_hash_map.iter_range(96..).next_back());
+
+    println!();
+
+    // Edit the chain like a list, from a cursor at a key
+    let mut edited_linked_hash_map: LinkedHashMap<_, _> = (1..=5).map(|i| (i, i * 10)).collect();
+
+    if let Some(mut cursor) = edited_linked_hash_map.cursor_at(&2) {
+        cursor.insert_after(25, 250);
+        cursor.move_next();
+
+        if let Some((_, val)) = cursor.current() {
+            *val += 1;
+        }
+
+        cursor.move_next();
+        println!("{:?}", cursor.remove_current());
+        println!("{:?} {:?}", cursor.peek_prev(), cursor.key());
+    }
+
+    println!("{:?}", edited_linked_hash_map);
+
+    println!();
+
+    // Look up String keys by a precomputed hash of a &str, without allocating
+    let mut raw_linked_hash_map: LinkedHashMap<String, u32> = LinkedHashMap::new();
+    raw_linked_hash_map.insert("Apple".to_string(), 1);
+
+    let hash = raw_linked_hash_map.hasher().hash_one("Apple");
+    println!("{:?}", raw_linked_hash_map.raw_entry().from_hash(hash, |key| key == "Apple"));
+
+    let hash = raw_linked_hash_map.hasher().hash_one("Banana");
+    let (key, val) = raw_linked_hash_map
+        .raw_entry_mut()
+        .from_hash(hash, |key| key == "Banana")
+        .or_insert_with(|| ("Banana".to_string(), 2));
+    println!("{} {}", key, val);
+
+    println!("{:?}", raw_linked_hash_map.get(&"Banana".to_string()));
+
+    println!();
+
+    // Query String keys with a &str, without allocating a String
+    let mut borrowed_linked_hash_map: LinkedHashMap<String, u32> = LinkedHashMap::new();
+    borrowed_linked_hash_map.insert("Borrowed".to_string(), 1);
+    borrowed_linked_hash_map["Borrowed"] += 1;
+
+    println!("{:?} {}", borrowed_linked_hash_map.get("Borrowed"), borrowed_linked_hash_map.contains_key("Owned"));
+    println!("{:?}", borrowed_linked_hash_map.remove("Borrowed"));
+
+    println!();
+
+    // Ordered membership only, with a set
+    let mut linked_hash_set: LinkedHashSet<_> = vec!["Red", "Green", "Red", "Blue"].into_iter().collect();
+    println!("{} {}", linked_hash_set.insert("Green"), linked_hash_set.insert("Yellow"));
+    linked_hash_set.remove("Red");
+
+    println!("{:?} {}", linked_hash_set, linked_hash_set.contains("Blue"));
+
+    println!();
+
+    // Several values per key, as with repeated HTTP headers
+    let mut headers = LinkedHashMultimap::new();
+    headers.insert("Accept", "text/html");
+    headers.insert("Cookie", "a=1");
+    headers.insert("Accept", "application/json");
+
+    println!("{:?} {:?}", headers.get("Accept"), headers.get_all("Accept"));
+    println!("{:?}", headers.iter().collect::<Vec<_>>());
+    println!("{} {} {:?}", headers.len(), headers.keys_len(), headers);
+
+    println!();
+
+    // Cache entries which expire after their time to live
+    let mut expiring_cache = LruCache::with_capacity(10);
+    expiring_cache.put_with_ttl("Session", 1, Duration::from_millis(20));
+    expiring_cache.put_with_ttl("Token", 2, Duration::from_secs(60));
+    expiring_cache.put("Config", 3);
+
+    std::thread::sleep(Duration::from_millis(40));
+
+    println!("{:?} {:?}", expiring_cache.peek("Session"), expiring_cache.iter().collect::<Vec<_>>());
+    println!("{} {}", expiring_cache.evict_expired(), expiring_cache.len());
+
+    println!();
+
+    // Clean up after every entry the cache lets go of
+    let removals = Arc::new(Mutex::new(Vec::new()));
+    let mut listening_cache = LruCache::with_capacity(2);
+    let listener_removals = Arc::clone(&removals);
+    listening_cache.set_removal_listener(move |key, _, cause| listener_removals.lock().unwrap().push((key, cause)));
+
+    listening_cache.put("First", 1);
+    listening_cache.put("Second", 2);
+    listening_cache.put("Third", 3);
+    listening_cache.invalidate("Second");
+    listening_cache.clear();
+
+    println!("{:?}", removals.lock().unwrap());
+
+    println!();
+
+    // Bound the cache by the total size of its values rather than their count
+    let mut weighted_cache = LruCache::with_max_weight(10, |_, val: &String| val.len());
+    weighted_cache.put("Short", "abc".to_string());
+    weighted_cache.put("Medium", "abcdef".to_string());
+    weighted_cache.put("Long", "abcdefg".to_string());
+
+    println!("{:?} {}", weighted_cache.iter().collect::<Vec<_>>(), weighted_cache.weight());
+
+    println!();
+
+    // Monitor how well a cache is doing
+    let mut monitored_cache = LruCache::with_capacity(2);
+    monitored_cache.put(1, "One");
+    monitored_cache.put(2, "Two");
+    monitored_cache.get(&1);
+    monitored_cache.get(&3);
+    monitored_cache.put(3, "Three");
+
+    let stats = monitored_cache.stats();
+    println!("{:?} {:?}", stats, stats.hit_rate());
+
+    println!();
+
+    // Read a value, computing it only on the first access
+    let mut computed_linked_hash_map = LinkedHashMap::new();
+    *computed_linked_hash_map.get_or_insert_with("Expensive", || 40) += 1;
+    *computed_linked_hash_map.get_or_insert_with("Expensive", || unreachable!()) += 1;
+
+    let mut computed_cache = LruCache::with_capacity(1);
+    computed_cache.get_or_insert_with("Report", || "Rendered");
+    computed_cache.get_or_insert_with("Report", || unreachable!());
+
+    println!("{:?} {:?}", computed_linked_hash_map, computed_cache.stats());
+
+    println!();
+
+    // Swap values in place without ever moving or adding an entry
+    let mut replaced_linked_hash_map = LinkedHashMap::new();
+    replaced_linked_hash_map.insert("Left", 1);
+    replaced_linked_hash_map.insert("Right", 2);
+
+    let old_left = replaced_linked_hash_map.replace("Left", 10);
+    let old_middle = replaced_linked_hash_map.replace("Middle", 20);
+
+    println!("{:?} {:?} {:?}", old_left, old_middle, replaced_linked_hash_map);
+
+    println!();
+
+    // Merge config layers, with the later layer overriding the earlier one
+    let mut base_layer = LinkedHashMap::new();
+    base_layer.insert("Theme", "Light");
+    base_layer.insert("Font", "Serif");
+
+    let mut user_layer = LinkedHashMap::new();
+    user_layer.insert("Theme", "Dark");
+    user_layer.insert("Zoom", "125%");
+
+    base_layer.append(&mut user_layer);
+
+    println!("{:?} {:?}", base_layer, user_layer);
+
+    println!();
+
+    // Split a playlist into what has played and what is still queued
+    let mut played_linked_hash_map: LinkedHashMap<_, _> = (1..=5).map(|track| (track, track * 60)).collect();
+    let queued_linked_hash_map = played_linked_hash_map.split_off_key(&3).unwrap_or_default();
+    let last_linked_hash_map = queued_linked_hash_map.clone().split_off(2);
+
+    println!("{:?} {:?} {:?}", played_linked_hash_map, queued_linked_hash_map, last_linked_hash_map);
+
+    println!();
+
+    // Keep only the first few signups
+    let mut signups_linked_hash_map = LinkedHashMap::new();
+
+    for (i, name) in ["Ada", "Grace", "Linus", "Ken"].iter().enumerate() {
+        signups_linked_hash_map.insert(*name, i);
+    }
+
+    signups_linked_hash_map.truncate(2);
+
+    println!("{:?}", signups_linked_hash_map);
+
+    println!();
+
+    // Keep a feed newest first
+    let mut feed_linked_hash_map = LinkedHashMap::new();
+    feed_linked_hash_map.insert("Monday", "Hello");
+    feed_linked_hash_map.insert("Tuesday", "Update");
+    feed_linked_hash_map.insert("Wednesday", "Release");
+    feed_linked_hash_map.reverse();
+
+    println!("{:?} {:?}", feed_linked_hash_map, feed_linked_hash_map.get_index_of("Monday"));
+
+    println!();
+
+    // Filter stock in one pass, restocking what is kept
+    let mut stock_linked_hash_map = LinkedHashMap::new();
+    stock_linked_hash_map.insert("Apples", 3);
+    stock_linked_hash_map.insert("Pears", 0);
+    stock_linked_hash_map.insert("Plums", 5);
+    stock_linked_hash_map.retain(|_, count| *count > 0);
+    stock_linked_hash_map.retain_mut(|_, count| {
+        *count += 10;
+        true
+    });
+
+    println!("{:?}", stock_linked_hash_map);
+
+    println!();
+
+    // Purge the stale sessions of a long lived map
+    let mut sessions_linked_hash_map: LinkedHashMap<_, _> = (1..=6).map(|id| (id, id * 10)).collect();
+    let stale: Vec<_> = sessions_linked_hash_map.extract_if(|_, idle| *idle > 30).collect();
+
+    println!("{:?} {:?}", stale, sessions_linked_hash_map);
+
+    println!();
+
+    // Visit every entry in place, without an iterator in between
+    let mut scores_linked_hash_map: LinkedHashMap<_, _> = [("Red", 3), ("Blue", 5)].iter().copied().collect();
+    scores_linked_hash_map.for_each_mut(|_, score| *score *= 2);
+    scores_linked_hash_map.for_each(|team, score| println!("{} {}", team, score));
+
+    println!();
+
+    // Write a map out in literal order
+    let literal_linked_hash_map = linkedhashmap::linked_hash_map! {
+        "Zulu" => 26,
+        "Alpha" => 1,
+        "Mike" => 13,
+    };
+    let empty_linked_hash_map: LinkedHashMap<&str, i32> = linkedhashmap::linked_hash_map! {};
+
+    println!("{:?} {:?}", literal_linked_hash_map, empty_linked_hash_map);
+
+    println!();
+
+    // Build a small ordered map from an array
+    let array_linked_hash_map = LinkedHashMap::from([("One", 1), ("Two", 2), ("Three", 3)]);
+
+    println!("{:?}", array_linked_hash_map);
+
+    println!();
+
+    // Convert to and from ordered lists of pairs
+    let pairs_linked_hash_map: LinkedHashMap<_, _> = LinkedHashMap::from(vec![("Up", 1), ("Down", 2), ("Up", 3)]);
+    let snapshot_pairs = pairs_linked_hash_map.to_vec();
+    let owned_pairs: Vec<_> = pairs_linked_hash_map.into();
+
+    println!("{:?} {}", owned_pairs, snapshot_pairs == owned_pairs);
+
+    println!();
+
+    // Refuse to overwrite a key by accident
+    let mut accounts_linked_hash_map = LinkedHashMap::new();
+    *accounts_linked_hash_map.try_insert("Alice", 100).unwrap() += 5;
+
+    if let Err(error) = accounts_linked_hash_map.try_insert("Alice", 0) {
+        println!("{}", error);
+    }
+
+    println!("{:?}", accounts_linked_hash_map);
+
+    println!();
+
+    // Move an amount from one entry to another without cloning either
+    let mut balances_linked_hash_map = LinkedHashMap::from([("Checking", 50), ("Savings", 200)]);
+
+    let accounts = ["Savings", "Checking", "Brokerage"];
+
+    if let [Some(from), Some(to), None] = balances_linked_hash_map.get_disjoint_mut(accounts) {
+        *from -= 75;
+        *to += 75;
+    }
+
+    println!("{:?}", balances_linked_hash_map);
+
+    println!();
+
+    // Canonicalize keys in place, keeping their order
+    let mut options_linked_hash_map = LinkedHashMap::from([("COLOR", "auto"), ("pager", "less")]);
+    let old_key = options_linked_hash_map.replace_key("COLOR", "color");
+    let clash = options_linked_hash_map.replace_key("color", "pager");
+
+    println!("{:?} {:?} {:?}", old_key, clash, options_linked_hash_map);
+
+    println!();
+
+    // Look a key up once, then promote or remove it through its entry
+    let mut recent_linked_hash_map = LinkedHashMap::from([("Old", 1), ("Middle", 2), ("New", 3)]);
+
+    if let Entry::Occupied(mut entry) = recent_linked_hash_map.entry("Old") {
+        entry.to_back();
+    }
+
+    if let Entry::Occupied(mut entry) = recent_linked_hash_map.entry("New") {
+        entry.to_front();
+    }
+
+    let removed = match recent_linked_hash_map.entry("Middle") {
+        Entry::Occupied(entry) => Some(entry.remove_entry()),
+        Entry::Vacant(_) => None,
+    };
+
+    println!("{:?} {:?}", removed, recent_linked_hash_map);
+
+    println!();
+
+    // Plug in another eviction policy, here evicting the newest entry
+    let mut selector_cache = LruCache::with_capacity(2);
+    selector_cache.put("Pinned", 1);
+    selector_cache.put("Scratch", 2);
+
+    let victim = selector_cache.put_with_selector("Next", 3, |cache| *cache.iter().next_back().unwrap().0);
+
+    println!("{:?} {:?}", victim, selector_cache.iter().collect::<Vec<_>>());
+
+    println!();
+
+    // Hash small keys with FxHash, when the fxhash feature is enabled
+    #[cfg(feature = "fxhash")]
+    {
+        let mut fx_linked_hash_map = linkedhashmap::FxLinkedHashMap::default();
+        fx_linked_hash_map.extend((0..5).map(|i| (i, i * 10)));
+
+        println!("{:?}", fx_linked_hash_map);
+    }
+}
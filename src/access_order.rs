@@ -0,0 +1,147 @@
+use crate::{node, node_mut, Entry, Iter, LinkedHashMap};
+
+// LinkedHashMap kept in order of access rather than of insertion,
+// like the access-ordered LinkedHashMap of Java.
+// Every successful lookup through get or get_mut, and every insert,
+// relinks the entry to the back, so the front is always the least recently used.
+// Lookups reorder the chain, which is why they take the map mutably
+pub struct AccessOrderMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    map: LinkedHashMap<K, V>,
+}
+
+impl<K, V> AccessOrderMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    pub fn new() -> AccessOrderMap<K, V> {
+        AccessOrderMap {
+            map: LinkedHashMap::new(),
+        }
+    }
+
+    // Inserting an existing key counts as an access to it
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.map.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.to_back();
+                Some(entry.insert(val))
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(val);
+                None
+            }
+        }
+    }
+
+    // The key is looked up once, and the node it found is read after relinking
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let index = self.map.move_to_back_index(key)?;
+
+        Some(&node(&self.map.nodes, index).val)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let index = self.map.move_to_back_index(key)?;
+
+        Some(&mut node_mut(&mut self.map.nodes, index).val)
+    }
+
+    // Value of the key, computing and inserting it first if the key is missing.
+    // Either way counts as an access
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        match self.map.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.to_back();
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => entry.insert(f()),
+        }
+    }
+
+    // Looks at a value without counting as an access
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    // Modifies a value in place without counting as an access
+    pub fn peek_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.get_mut(key)
+    }
+
+    // Least recently used entry, which pop_front would remove next
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.map.front()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.remove(key)
+    }
+
+    // Removes and returns the least recently used entry
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        self.map.pop_front()
+    }
+
+    // Iterates from the least to the most recently used entry
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        self.map.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // Underlying map, for reading it without counting as an access
+    pub fn as_map(&self) -> &LinkedHashMap<K, V> {
+        &self.map
+    }
+
+    // Underlying map, for changes that do not count as accesses either
+    pub(crate) fn as_map_mut(&mut self) -> &mut LinkedHashMap<K, V> {
+        &mut self.map
+    }
+}
+
+impl<K, V> Default for AccessOrderMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    fn default() -> AccessOrderMap<K, V> {
+        AccessOrderMap::new()
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{node, node_mut, LinkedHashMap, NodeIndex, NIL};
+
+// Cursor over the chain of a LinkedHashMap, for editing it like a list.
+// The cursor points at an entry, or at the ghost position past the back
+// and before the front, where it starts on an empty map.
+// Moving past either end lands on the ghost, and moving on from it wraps around.
+// Every edit goes through the map itself, so its table stays consistent
+pub struct CursorMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+    current: NodeIndex,
+}
+
+impl<'a, K, V, S> CursorMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub(crate) fn new(map: &'a mut LinkedHashMap<K, V, S>, current: NodeIndex) -> CursorMut<'a, K, V, S> {
+        CursorMut { map, current }
+    }
+
+    pub fn move_next(&mut self) {
+        self.current = match self.current {
+            NIL => self.map.first_link,
+            current => node(&self.map.nodes, current).next,
+        };
+    }
+
+    pub fn move_prev(&mut self) {
+        self.current = match self.current {
+            NIL => self.map.current_link,
+            current => node(&self.map.nodes, current).prev,
+        };
+    }
+
+    pub fn key(&self) -> Option<&K> {
+        let (key, _) = self.map.pair_at(self.current)?;
+
+        Some(key)
+    }
+
+    // Current entry, with its value open to modification
+    pub fn current(&mut self) -> Option<(&K, &mut V)> {
+        if self.current == NIL {
+            return None;
+        }
+
+        let node = node_mut(&mut self.map.nodes, self.current);
+
+        Some((&node.key, &mut node.val))
+    }
+
+    pub fn peek_next(&self) -> Option<(&K, &V)> {
+        match self.current {
+            NIL => self.map.pair_at(self.map.first_link),
+            current => self.map.pair_at(node(&self.map.nodes, current).next),
+        }
+    }
+
+    pub fn peek_prev(&self) -> Option<(&K, &V)> {
+        match self.current {
+            NIL => self.map.pair_at(self.map.current_link),
+            current => self.map.pair_at(node(&self.map.nodes, current).prev),
+        }
+    }
+
+    // Removes the current entry and moves on to the next one,
+    // which makes removing while walking the chain safe
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        if self.current == NIL {
+            return None;
+        }
+
+        let removed = self.current;
+        self.current = node(&self.map.nodes, removed).next;
+
+        Some(self.map.remove_node(removed))
+    }
+
+    // Inserts the entry right before the cursor, or at the back on the ghost.
+    // As with the insert_before of the map, an existing key is moved there
+    // with its value replaced, and the current key only has its value replaced
+    pub fn insert_before(&mut self, key: K, val: V) -> Option<V> {
+        if self.key() == Some(&key) {
+            return self.map.insert(key, val);
+        }
+
+        let (index, old_val) = self.map.insert_detached(key, val);
+
+        match self.current {
+            NIL => self.map.link_back(index),
+            current => self.map.link_before(index, current),
+        }
+
+        old_val
+    }
+
+    // Inserts the entry right after the cursor, or at the front on the ghost
+    pub fn insert_after(&mut self, key: K, val: V) -> Option<V> {
+        if self.key() == Some(&key) {
+            return self.map.insert(key, val);
+        }
+
+        let (index, old_val) = self.map.insert_detached(key, val);
+
+        match self.current {
+            NIL => self.map.link_front(index),
+            current => self.map.link_after(index, current),
+        }
+
+        old_val
+    }
+}
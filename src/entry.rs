@@ -0,0 +1,214 @@
+use crate::{node, node_mut, DefaultHashBuilder, LinkedHashMap, NodeIndex};
+
+// View into a single key of a LinkedHashMap, found with one lookup in the table,
+// which is either already in the map or can be inserted at the end of it
+pub enum Entry<'a, K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    Occupied(OccupiedEntry<'a, K, V, S>),
+    Vacant(VacantEntry<'a, K, V, S>),
+}
+
+// An occupied entry remembers where its node was found,
+// so that reading or removing it never looks the key up again
+pub struct OccupiedEntry<'a, K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+    pos: usize,
+    index: NodeIndex,
+}
+
+// A vacant entry keeps the hash of its key for the insertion it makes
+pub struct VacantEntry<'a, K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+    key: K,
+    hash: u64,
+}
+
+impl<'a, K, V, S> Entry<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub(crate) fn new(map: &'a mut LinkedHashMap<K, V, S>, key: K) -> Entry<'a, K, V, S> {
+        match map.find(&key) {
+            Some((pos, index)) => Entry::Occupied(OccupiedEntry { map, pos, index }),
+            None => {
+                let hash = map.hash_key(&key);
+                Entry::Vacant(VacantEntry { map, key, hash })
+            }
+        }
+    }
+
+    pub fn key(&self) -> &K {
+        match *self {
+            Entry::Occupied(ref entry) => entry.key(),
+            Entry::Vacant(ref entry) => entry.key(),
+        }
+    }
+
+    // A vacant key is inserted at the end of the chain, as with insert
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    // Modifies an occupied value in place, leaving a vacant entry untouched
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Entry<'a, K, V, S> {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K, V, S> OccupiedEntry<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        &node(&self.map.nodes, self.index).key
+    }
+
+    pub fn get(&self) -> &V {
+        &node(&self.map.nodes, self.index).val
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut node_mut(&mut self.map.nodes, self.index).val
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut node_mut(&mut self.map.nodes, self.index).val
+    }
+
+    // Replaces the value, keeping the entry in its position
+    pub fn insert(&mut self, val: V) -> V {
+        core::mem::replace(self.get_mut(), val)
+    }
+
+    pub fn remove(self) -> V {
+        let (_, val) = self.remove_entry();
+
+        val
+    }
+
+    // Removes the entry from the map, handing back the stored key as well
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_at(self.pos, self.index)
+    }
+
+    // Relinks the entry to the end of the chain, as move_to_back does, but
+    // without looking the key up again. The position in the table is unaffected.
+    // Both ends are cheap to link at: the chain is relinked in O(1), and the
+    // positions in O(log n)
+    pub fn to_back(&mut self) {
+        self.map.unlink(self.index);
+        self.map.link_back(self.index);
+    }
+
+    // Relinks the entry to the beginning of the chain, as move_to_front does,
+    // at the same cost as to_back
+    pub fn to_front(&mut self) {
+        self.map.unlink(self.index);
+        self.map.link_front(self.index);
+    }
+}
+
+impl<'a, K, V, S> VacantEntry<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    // Inserts the value at the end of the chain
+    pub fn insert(self, val: V) -> &'a mut V {
+        let index = self.map.push_back(self.hash, self.key, val);
+
+        &mut node_mut(&mut self.map.nodes, index).val
+    }
+}
+
+// Returned by try_insert when the key is already in the map,
+// holding the occupied entry along with the value that was not inserted
+pub struct OccupiedError<'a, K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub entry: OccupiedEntry<'a, K, V, S>,
+    pub value: V,
+}
+
+impl<'a, K, V, S> core::fmt::Debug for OccupiedError<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq + core::fmt::Debug,
+    V: core::fmt::Debug,
+    S: core::hash::BuildHasher,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", self.entry.key())
+            .field("old_value", self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, K, V, S> core::fmt::Display for OccupiedError<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq + core::fmt::Debug,
+    V: core::fmt::Debug,
+    S: core::hash::BuildHasher,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists with value {:?}",
+            self.value,
+            self.entry.key(),
+            self.entry.get(),
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V, S> std::error::Error for OccupiedError<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq + core::fmt::Debug,
+    V: core::fmt::Debug,
+    S: core::hash::BuildHasher,
+{
+}
@@ -0,0 +1,2833 @@
+// Without the default std feature, only core and alloc are used,
+// so the map also works in embedded and kernel contexts
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod access_order;
+mod cursor;
+mod entry;
+#[cfg(any(feature = "fxhash", not(feature = "std")))]
+mod fxhash;
+mod linked_hash_set;
+mod lru_cache;
+mod macros;
+mod multimap;
+mod positions;
+mod raw_entry;
+#[cfg(feature = "std")]
+mod sharded_map;
+#[cfg(feature = "std")]
+mod sync_map;
+mod table;
+
+pub use access_order::AccessOrderMap;
+pub use cursor::CursorMut;
+pub use entry::{Entry, OccupiedEntry, OccupiedError, VacantEntry};
+#[cfg(any(feature = "fxhash", not(feature = "std")))]
+pub use fxhash::{FxBuildHasher, FxHasher, FxLinkedHashMap};
+pub use linked_hash_set::{LinkedHashSet, SetIntoIter, SetIter};
+pub use lru_cache::{CacheIter, CacheStats, LruCache, RemovalCause};
+pub use multimap::{LinkedHashMultimap, MultiIter};
+pub use raw_entry::{RawEntryBuilder, RawEntryBuilderMut, RawEntryMut, RawOccupiedEntryMut, RawVacantEntryMut};
+#[cfg(feature = "std")]
+pub use sharded_map::ShardedLinkedHashMap;
+#[cfg(feature = "std")]
+pub use sync_map::SyncLinkedHashMap;
+
+use {
+    alloc::{collections::TryReserveError, vec, vec::Vec},
+    core::iter::FusedIterator,
+    positions::Positions,
+    table::IndexTable,
+};
+
+#[cfg(feature = "std")]
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    time::Instant,
+};
+
+// Hasher builder of maps that do not name one.
+// The random keys of the std RandomState need the std feature,
+// so without it keys are hashed with FxHash instead.
+// FxHash is unkeyed and not DoS resistant: anyone who picks the keys can make
+// them all collide. A no_std map holding untrusted keys should be built with
+// with_hasher and a keyed hasher seeded from a source of entropy.
+// Keys that only differ in their high bits, like aligned addresses, are fine,
+// since the table mixes every hash before picking a bucket
+#[cfg(feature = "std")]
+pub type DefaultHashBuilder = RandomState;
+#[cfg(not(feature = "std"))]
+pub type DefaultHashBuilder = FxBuildHasher;
+
+// Position of a node in the slab.
+// The links of the Doubly Linked List are indices rather than pointers,
+// so the nodes live side by side in one Vec instead of one allocation each,
+// and need neither reference counting nor runtime borrow checks
+type NodeIndex = u32;
+
+// Stands in for a missing link, before the first or after the last node
+const NIL: NodeIndex = NodeIndex::MAX;
+
+// Each entry of the Doubly Linked List, owning its key and value.
+// A Doubly linked list is used instead of a Single (Forward) linked list
+// so that removal of intermediate elements in the list can occur
+#[derive(Clone)]
+struct Node<K, V> {
+    key: K,
+    val: V,
+    prev: NodeIndex,
+    next: NodeIndex,
+    // The insertion this entry was added by
+    insertion: u64,
+    // Hash the node was filed under in the table
+    hash: u64,
+}
+
+// Every slot of the slab either holds a node,
+// or is vacant and points to the next vacant slot to be reused
+#[derive(Clone)]
+enum Slot<K, V> {
+    Occupied(Node<K, V>),
+    Vacant(NodeIndex),
+}
+
+// Sorts the values with a bottom up merge sort, returning how many pairs were
+// out of order. Whenever a value is taken from the right run, it comes before
+// every value still left in the left run
+fn count_inversions(values: &mut Vec<usize>) -> usize {
+    let mut buffer = vec![0; values.len()];
+    let mut inversions = 0;
+    let mut width = 1;
+
+    while width < values.len() {
+        for start in (0..values.len()).step_by(2 * width) {
+            let mid = (start + width).min(values.len());
+            let end = (start + 2 * width).min(values.len());
+            let (mut left, mut right) = (start, mid);
+
+            for slot in &mut buffer[start..end] {
+                if right == end || (left < mid && values[left] <= values[right]) {
+                    *slot = values[left];
+                    left += 1;
+                } else {
+                    *slot = values[right];
+                    inversions += mid - left;
+                    right += 1;
+                }
+            }
+        }
+
+        core::mem::swap(values, &mut buffer);
+        width *= 2;
+    }
+
+    inversions
+}
+
+// Links only ever point at occupied slots
+fn node<K, V>(nodes: &[Slot<K, V>], index: NodeIndex) -> &Node<K, V> {
+    match nodes[index as usize] {
+        Slot::Occupied(ref node) => node,
+        Slot::Vacant(_) => unreachable!("link to a vacant slot"),
+    }
+}
+
+fn node_mut<K, V>(nodes: &mut [Slot<K, V>], index: NodeIndex) -> &mut Node<K, V> {
+    match nodes[index as usize] {
+        Slot::Occupied(ref mut node) => node,
+        Slot::Vacant(_) => unreachable!("link to a vacant slot"),
+    }
+}
+
+// Separate Iter struct provides
+// iteration from either end through current state of a
+// LinkedHashMap, borrowing each key and value from it.
+// The count of entries left between both ends
+// keeps them from passing each other
+pub struct Iter<'a, K, V> {
+    nodes: &'a [Slot<K, V>],
+    iter_link: NodeIndex,
+    back_link: NodeIndex,
+    remaining: usize,
+}
+
+impl<'a, K, V> core::iter::Iterator for Iter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = node(self.nodes, self.iter_link);
+        self.iter_link = node.next;
+        self.remaining -= 1;
+
+        Some((&node.key, &node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Iter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = node(self.nodes, self.back_link);
+        self.back_link = node.prev;
+        self.remaining -= 1;
+
+        Some((&node.key, &node.val))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Iter<'a, K, V> {}
+
+// Iteration in order of insertion, mutably borrowing each value.
+// Safe code cannot borrow slab slots one at a time while following links,
+// so the iterator keeps a raw pointer to the slab it borrows for 'a and
+// borrows one slot through it at every step, without allocating
+pub struct IterMut<'a, K, V> {
+    nodes: *mut Slot<K, V>,
+    iter_link: NodeIndex,
+    back_link: NodeIndex,
+    remaining: usize,
+    marker: core::marker::PhantomData<&'a mut [Slot<K, V>]>,
+}
+
+// Only the keys are shared and the values handed out, as by &'a K and &'a mut V
+unsafe impl<'a, K: Sync, V: Send> Send for IterMut<'a, K, V> {}
+unsafe impl<'a, K: Sync, V: Sync> Sync for IterMut<'a, K, V> {}
+
+impl<'a, K, V> IterMut<'a, K, V> {
+    // Borrows the node at a link for the rest of 'a.
+    // Both ends only ever step onto nodes neither has handed out yet, and
+    // stop once `remaining` says they have met, so no node is borrowed twice.
+    // The slab stays mutably borrowed for 'a, so it is neither moved nor
+    // touched by anything else while the borrows live
+    fn borrow_node(&mut self, index: NodeIndex) -> &'a mut Node<K, V> {
+        // SAFETY: links only point at occupied slots within the slab, and
+        // each slot is borrowed at most once, as explained above
+        match unsafe { &mut *self.nodes.add(index as usize) } {
+            Slot::Occupied(node) => node,
+            Slot::Vacant(_) => unreachable!("link to a vacant slot"),
+        }
+    }
+}
+
+impl<'a, K, V> core::iter::Iterator for IterMut<'a, K, V> {
+    type Item = (&'a K, &'a mut V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.borrow_node(self.iter_link);
+        self.iter_link = node.next;
+        self.remaining -= 1;
+
+        Some((&node.key, &mut node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for IterMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.borrow_node(self.back_link);
+        self.back_link = node.prev;
+        self.remaining -= 1;
+
+        Some((&node.key, &mut node.val))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for IterMut<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for IterMut<'a, K, V> {}
+
+// Iteration over only the keys or only the values, in order of insertion.
+// Each one wraps the iterator over pairs and drops the half it does not need
+pub struct Keys<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> core::iter::Iterator for Keys<'a, K, V> {
+    type Item = &'a K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Keys<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Keys<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Keys<'a, K, V> {}
+
+pub struct Values<'a, K, V> {
+    inner: Iter<'a, K, V>,
+}
+
+impl<'a, K, V> core::iter::Iterator for Values<'a, K, V> {
+    type Item = &'a V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, val)| val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for Values<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, val)| val)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Values<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for Values<'a, K, V> {}
+
+pub struct ValuesMut<'a, K, V> {
+    inner: IterMut<'a, K, V>,
+}
+
+impl<'a, K, V> core::iter::Iterator for ValuesMut<'a, K, V> {
+    type Item = &'a mut V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, val)| val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for ValuesMut<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, val)| val)
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for ValuesMut<'a, K, V> {}
+
+impl<'a, K, V> FusedIterator for ValuesMut<'a, K, V> {}
+
+// Consuming iteration from either end, moving each node out of the slab
+// taken over from the map
+pub struct IntoIter<K, V> {
+    nodes: Vec<Slot<K, V>>,
+    iter_link: NodeIndex,
+    back_link: NodeIndex,
+    remaining: usize,
+}
+
+impl<K, V> IntoIter<K, V> {
+    fn take_node(&mut self, index: NodeIndex) -> Node<K, V> {
+        match core::mem::replace(&mut self.nodes[index as usize], Slot::Vacant(NIL)) {
+            Slot::Occupied(node) => node,
+            Slot::Vacant(_) => unreachable!("link to a vacant slot"),
+        }
+    }
+}
+
+impl<K, V> core::iter::Iterator for IntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.take_node(self.iter_link);
+        self.iter_link = node.next;
+        self.remaining -= 1;
+
+        Some((node.key, node.val))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoIter<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = self.take_node(self.back_link);
+        self.back_link = node.prev;
+        self.remaining -= 1;
+
+        Some((node.key, node.val))
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoIter<K, V> {}
+
+impl<K, V> FusedIterator for IntoIter<K, V> {}
+
+// Consuming iteration over only the keys or only the values
+pub struct IntoKeys<K, V> {
+    inner: IntoIter<K, V>,
+}
+
+impl<K, V> core::iter::Iterator for IntoKeys<K, V> {
+    type Item = K;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(key, _)| key)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoKeys<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(key, _)| key)
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoKeys<K, V> {}
+
+impl<K, V> FusedIterator for IntoKeys<K, V> {}
+
+pub struct IntoValues<K, V> {
+    inner: IntoIter<K, V>,
+}
+
+impl<K, V> core::iter::Iterator for IntoValues<K, V> {
+    type Item = V;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(_, val)| val)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for IntoValues<K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(_, val)| val)
+    }
+}
+
+impl<K, V> ExactSizeIterator for IntoValues<K, V> {}
+
+impl<K, V> FusedIterator for IntoValues<K, V> {}
+
+// Draining iteration from either end, popping each entry off the borrowed map.
+// Whatever is left unyielded is cleared when the iterator is dropped, so the
+// map always ends up empty, with its slab and table kept for reuse
+pub struct Drain<'a, K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+}
+
+impl<'a, K, V, S> core::iter::Iterator for Drain<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.map.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.map.len(), Some(self.map.len()))
+    }
+}
+
+impl<'a, K, V, S> DoubleEndedIterator for Drain<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.map.pop_back()
+    }
+}
+
+impl<'a, K, V, S> ExactSizeIterator for Drain<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+}
+
+impl<'a, K, V, S> FusedIterator for Drain<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+}
+
+impl<'a, K, V, S> Drop for Drain<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn drop(&mut self) {
+        self.map.clear();
+    }
+}
+
+// Walks the chain of the borrowed map once, removing and yielding only the
+// entries the closure holds for. Entries not yet reached when the iterator
+// is dropped stay in the map, as do the ones the closure rejected
+pub struct ExtractIf<'a, K, V, F, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+    iter_link: NodeIndex,
+    pred: F,
+}
+
+impl<'a, K, V, F, S> core::iter::Iterator for ExtractIf<'a, K, V, F, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+    F: FnMut(&K, &mut V) -> bool,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.iter_link != NIL {
+            let index = self.iter_link;
+            let node = node_mut(&mut self.map.nodes, index);
+            self.iter_link = node.next;
+
+            if (self.pred)(&node.key, &mut node.val) {
+                return Some(self.map.remove_node(index));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.map.len()))
+    }
+}
+
+impl<'a, K, V, F, S> FusedIterator for ExtractIf<'a, K, V, F, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+    F: FnMut(&K, &mut V) -> bool,
+{
+}
+
+// Opaque marker of how many insertions a map had seen when it was taken,
+// used to find the entries inserted after it
+pub struct Checkpoint {
+    insertions: u64,
+}
+
+pub struct LinkedHashMap<K, V, S = DefaultHashBuilder>
+where
+    // Hash + Eq traits needed to find a key through the table
+    K: core::hash::Hash + Eq,
+    // Builds the hasher for the keys, DefaultHashBuilder unless one is supplied
+    S: core::hash::BuildHasher,
+{
+    // Slab of nodes, with vacant slots reused by later insertions
+    nodes: Vec<Slot<K, V>>,
+    // First vacant slot, with the rest chained through the vacant slots
+    vacant: NodeIndex,
+    first_link: NodeIndex,
+    current_link: NodeIndex,
+    // Finds the node of a key from its hash
+    table: IndexTable,
+    // Finds the position of a node in the chain, and the node at a position
+    positions: Positions,
+    hash_builder: S,
+    // Number of new keys inserted over the lifetime of the map
+    insertions: u64,
+}
+
+// With indices for links and no shared ownership, the map has the same
+// auto traits as the std HashMap: Send and Sync whenever its keys,
+// values and hasher are. Checked here so a later change cannot lose them
+#[allow(dead_code)]
+fn assert_send_sync() {
+    fn is_send_sync<T: Send + Sync>() {}
+
+    is_send_sync::<LinkedHashMap<Vec<u8>, Vec<u8>>>();
+    is_send_sync::<Iter<'_, Vec<u8>, Vec<u8>>>();
+    is_send_sync::<IntoIter<Vec<u8>, Vec<u8>>>();
+    is_send_sync::<Drain<'_, Vec<u8>, Vec<u8>>>();
+}
+
+impl<K, V> LinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    // Not a const fn, since the std RandomState picks random keys for its hasher.
+    // Default builds the same empty map for derived Default fields
+    pub fn new() -> LinkedHashMap<K, V> {
+        LinkedHashMap::with_hasher(DefaultHashBuilder::default())
+    }
+
+    // Room for `capacity` entries in both the slab and the table up front
+    pub fn with_capacity(capacity: usize) -> LinkedHashMap<K, V> {
+        LinkedHashMap::with_capacity_and_hasher(capacity, DefaultHashBuilder::default())
+    }
+}
+
+impl<K, V, S> LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    // Keys are hashed with hashers built by `hash_builder`
+    pub fn with_hasher(hash_builder: S) -> LinkedHashMap<K, V, S> {
+        LinkedHashMap::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> LinkedHashMap<K, V, S> {
+        LinkedHashMap {
+            nodes: Vec::with_capacity(capacity),
+            vacant: NIL,
+            first_link: NIL,
+            current_link: NIL,
+            table: IndexTable::with_capacity(capacity),
+            positions: Positions::new(),
+            hash_builder,
+            insertions: 0,
+        }
+    }
+
+    pub fn hasher(&self) -> &S {
+        &self.hash_builder
+    }
+
+    // A new key is linked at the end of the chain.
+    // An existing key keeps its position and only has its value replaced,
+    // with the previous value returned
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => Some(entry.insert(val)),
+            Entry::Vacant(entry) => {
+                entry.insert(val);
+                None
+            }
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+
+        Some(&node(&self.nodes, index).val)
+    }
+
+    // Stored key along with the value, for when the stored key instance
+    // differs from an equal key used for the lookup
+    pub fn get_key_value<Q>(&self, key: &Q) -> Option<(&K, &V)>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+
+        self.pair_at(index)
+    }
+
+    // Entry at the position in the chain, in O(log n)
+    pub fn get_index(&self, position: usize) -> Option<(&K, &V)> {
+        self.pair_at(self.positions.node_at(position)?)
+    }
+
+    // Position of the key in the chain, in O(log n).
+    // Positions are kept in a tree alongside the chain, which every link and
+    // unlink updates in O(log n), wherever in the chain it happens
+    pub fn get_index_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+
+        Some(self.positions.position(index))
+    }
+
+    // Oldest entry, found from the beginning of the chain
+    pub fn front(&self) -> Option<(&K, &V)> {
+        self.pair_at(self.first_link)
+    }
+
+    // Newest entry, found from the end of the chain
+    pub fn back(&self) -> Option<(&K, &V)> {
+        self.pair_at(self.current_link)
+    }
+
+    // Presence check against the table alone, without touching the value
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+
+        Some(&mut node_mut(&mut self.nodes, index).val)
+    }
+
+    // Inserts a new key at the end of the chain, but refuses to overwrite
+    // an existing one: the error hands back the value along with the entry
+    pub fn try_insert(&mut self, key: K, val: V) -> Result<&mut V, OccupiedError<'_, K, V, S>> {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Err(OccupiedError { entry, value: val }),
+            Entry::Vacant(entry) => Ok(entry.insert(val)),
+        }
+    }
+
+    // Renames the entry of the key in place, returning the old key.
+    // The node keeps its position in the chain and only moves in the table.
+    // The new key is handed back if the key is missing, or if the new key
+    // already belongs to another entry
+    pub fn replace_key<Q>(&mut self, key: &Q, new_key: K) -> Result<K, K>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (pos, index) = match self.find(key) {
+            Some(found) => found,
+            None => return Err(new_key),
+        };
+
+        if let Some((_, other_index)) = self.find::<K>(&new_key) {
+            if other_index != index {
+                return Err(new_key);
+            }
+        }
+
+        let hash = self.hash_key(&new_key);
+        self.table.remove(pos);
+        self.table.insert(hash, index);
+
+        let node = node_mut(&mut self.nodes, index);
+        node.hash = hash;
+
+        Ok(core::mem::replace(&mut node.key, new_key))
+    }
+
+    // Mutable borrows of several values at once, one per key, in the order
+    // of the keys, with None for a missing key.
+    // Panics if two of the keys find the same entry. The borrows are split off
+    // the slab in slab order, so no unsafe code is needed to hand them out
+    pub fn get_disjoint_mut<Q, const N: usize>(&mut self, keys: [&Q; N]) -> [Option<&mut V>; N]
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let indices = keys.map(|key| self.find(key).map(|(_, index)| index));
+
+        for (i, index) in indices.iter().enumerate() {
+            if index.is_some() && indices[..i].contains(index) {
+                panic!("duplicate keys passed to get_disjoint_mut");
+            }
+        }
+
+        // Key slots sorted by the slab index they found, missing keys last
+        let mut order: [usize; N] = core::array::from_fn(|i| i);
+        order.sort_unstable_by_key(|&i| indices[i].unwrap_or(NIL));
+
+        let mut vals: [Option<&mut V>; N] = core::array::from_fn(|_| None);
+        let mut rest: &mut [Slot<K, V>] = &mut self.nodes;
+        let mut offset = 0;
+
+        for i in order {
+            let index = match indices[i] {
+                Some(index) => index as usize,
+                None => break,
+            };
+
+            let (slot, tail) = core::mem::take(&mut rest)[index - offset..]
+                .split_first_mut()
+                .expect("found index lies in the slab");
+            rest = tail;
+            offset = index + 1;
+
+            vals[i] = match slot {
+                Slot::Occupied(node) => Some(&mut node.val),
+                Slot::Vacant(_) => unreachable!("table entry for a vacant slot"),
+            };
+        }
+
+        vals
+    }
+
+    // Swaps in a new value for an existing key, returning the old one.
+    // The entry never moves, and a missing key is left missing, with the
+    // value dropped and None returned
+    pub fn replace<Q>(&mut self, key: &Q, val: V) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.get_mut(key).map(|old| core::mem::replace(old, val))
+    }
+
+    // Every entry has exactly one bucket in the table,
+    // so the table already keeps the count in O(1)
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Number of entries the map holds before the slab or the table has to grow.
+    // Vacant slots are reused before the slab grows, so they count as room
+    pub fn capacity(&self) -> usize {
+        self.table.capacity().min(self.nodes.capacity())
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+        self.table.reserve(additional);
+    }
+
+    // Fallible form of reserve, for when running out of memory must not abort
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.nodes.try_reserve(additional)?;
+        self.table.try_reserve(additional)
+    }
+
+    // Vacant slots are kept where they are, since moving the nodes after them
+    // would change their indices. Only the spare room past the slab is released
+    pub fn shrink_to_fit(&mut self) {
+        self.nodes.shrink_to_fit();
+        self.table.shrink_to_fit();
+    }
+
+    // Removes every entry, leaving the map empty and reusable
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.table.clear();
+        self.positions.clear();
+        self.vacant = NIL;
+        self.first_link = NIL;
+        self.current_link = NIL;
+    }
+
+    // Moves every entry out in order while emptying the map
+    pub fn drain(&mut self) -> Drain<'_, K, V, S> {
+        Drain { map: self }
+    }
+
+    // Lazily removes the entries the closure holds for, yielding them in order
+    pub fn extract_if<F: FnMut(&K, &mut V) -> bool>(&mut self, pred: F) -> ExtractIf<'_, K, V, F, S> {
+        ExtractIf {
+            iter_link: self.first_link,
+            map: self,
+            pred,
+        }
+    }
+
+    // Cursor starting at the oldest entry
+    pub fn cursor_front_mut(&mut self) -> CursorMut<'_, K, V, S> {
+        let first_link = self.first_link;
+
+        CursorMut::new(self, first_link)
+    }
+
+    // Cursor starting at the newest entry
+    pub fn cursor_back_mut(&mut self) -> CursorMut<'_, K, V, S> {
+        let current_link = self.current_link;
+
+        CursorMut::new(self, current_link)
+    }
+
+    // Cursor starting at the entry of the key, if there is one
+    pub fn cursor_at<Q>(&mut self, key: &Q) -> Option<CursorMut<'_, K, V, S>>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+
+        Some(CursorMut::new(self, index))
+    }
+
+    // Lookups by a precomputed hash and a key comparison of the caller's own,
+    // for keys that are expensive to build or to hash twice
+    pub fn raw_entry(&self) -> RawEntryBuilder<'_, K, V, S> {
+        RawEntryBuilder::new(self)
+    }
+
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<'_, K, V, S> {
+        RawEntryBuilderMut::new(self)
+    }
+
+    // Value of the key, computing and inserting it first if the key is missing.
+    // Only looks the key up once
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        self.entry(key).or_insert_with(f)
+    }
+
+    // Looks the key up once, for inserting or updating it in place
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, S> {
+        Entry::new(self, key)
+    }
+
+    // Only clones the value out when the predicate holds on a borrow of it,
+    // so values the caller would discard are never copied
+    pub fn get_if<Q, F: FnOnce(&V) -> bool>(&self, key: &Q, pred: F) -> Option<V>
+    where
+        V: Clone,
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let val = self.get(key)?;
+
+        if pred(val) {
+            return Some(val.clone());
+        }
+
+        None
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, val) = self.remove_entry(key)?;
+
+        Some(val)
+    }
+
+    // Moves both the stored key and the value out of the map
+    pub fn remove_entry<Q>(&mut self, key: &Q) -> Option<(K, V)>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (pos, index) = self.find(key)?;
+
+        Some(self.remove_at(pos, index))
+    }
+
+    // Inserts the entry just before the anchor key instead of at the end.
+    // An existing key is moved there with its value replaced,
+    // unless it is the anchor itself. Panics if the anchor is not in the map
+    pub fn insert_before<Q>(&mut self, anchor: &Q, key: K, val: V) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, anchor_index) = self.find(anchor).expect("anchor key must be in the map");
+
+        if key.borrow() == anchor {
+            return self.insert(key, val);
+        }
+
+        let (index, old_val) = self.insert_detached(key, val);
+        self.link_before(index, anchor_index);
+
+        old_val
+    }
+
+    // Inserts the entry just after the anchor key instead of at the end,
+    // in the same way as insert_before
+    pub fn insert_after<Q>(&mut self, anchor: &Q, key: K, val: V) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, anchor_index) = self.find(anchor).expect("anchor key must be in the map");
+
+        if key.borrow() == anchor {
+            return self.insert(key, val);
+        }
+
+        let (index, old_val) = self.insert_detached(key, val);
+        self.link_after(index, anchor_index);
+
+        old_val
+    }
+
+    // Relinks every matching entry to the end of the chain.
+    // Matching entries keep their relative order, as do the entries left at the front
+    pub fn move_matching_to_back<F: FnMut(&K, &V) -> bool>(&mut self, mut f: F) {
+        let mut matching_links = Vec::new();
+
+        // Matches are gathered first, since relinking while walking the chain
+        // would revisit the moved links
+        self.walk(|index, key, val| {
+            if f(key, val) {
+                matching_links.push(index);
+            }
+            true
+        });
+
+        for index in matching_links {
+            self.unlink(index);
+            self.link_back(index);
+        }
+    }
+
+    // Relinks an entry to the end of the chain, as if it had just been inserted,
+    // without touching its key or value. Returns whether the key was found
+    pub fn move_to_back<Q>(&mut self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.move_to_back_index(key).is_some()
+    }
+
+    // Relinks an entry to the beginning of the chain, making it the oldest
+    pub fn move_to_front<Q>(&mut self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let index = match self.find(key) {
+            Some((_, index)) => index,
+            None => return false,
+        };
+
+        self.unlink(index);
+        self.link_front(index);
+
+        true
+    }
+
+    // Pairs each key with the accumulation of every value up to and including its own,
+    // in order of insertion
+    pub fn scan_values<B: Clone, F: FnMut(&B, &V) -> B>(&self, init: B, mut f: F) -> Vec<(K, B)>
+    where
+        K: Clone,
+    {
+        let mut scanned = Vec::new();
+        let mut acc = init;
+
+        self.walk(|_, key, val| {
+            acc = f(&acc, val);
+            scanned.push((K::clone(key), acc.clone()));
+            true
+        });
+
+        scanned
+    }
+
+    // Key whose measured value lies nearest to the target.
+    // Ties go to the entry inserted first
+    pub fn closest_by_value<F: FnMut(&V) -> i64>(&self, target: i64, mut metric: F) -> Option<K>
+    where
+        K: Clone,
+    {
+        let mut closest: Option<(u64, K)> = None;
+
+        self.walk(|_, key, val| {
+            let distance = metric(val).abs_diff(target);
+
+            // Strictly closer entries only, so an earlier entry keeps a tie
+            let is_closer = match closest {
+                Some((closest_distance, _)) => distance < closest_distance,
+                None => true,
+            };
+
+            if is_closer {
+                closest = Some((distance, K::clone(key)));
+            }
+            true
+        });
+
+        closest.map(|(_, key)| key)
+    }
+
+    // Exchanges the whole contents of two maps in O(1),
+    // by trading the slabs and tables rather than moving any entries
+    pub fn swap(&mut self, other: &mut LinkedHashMap<K, V, S>) {
+        core::mem::swap(self, other);
+    }
+
+    // Moves every entry of the other map onto the end of this one, in its order,
+    // leaving the other map empty but with its allocations kept.
+    // A key already in this map keeps its position and takes the value
+    // from the other map, as with insert, so later layers override earlier ones
+    pub fn append(&mut self, other: &mut LinkedHashMap<K, V, S>) {
+        self.reserve(other.len());
+
+        while let Some((key, val)) = other.pop_front() {
+            self.insert(key, val);
+        }
+    }
+
+    // Moves the entries from the position onwards into a new map with a clone
+    // of the hasher, keeping the order of both halves.
+    // The moved nodes take their stored hashes along, so no key is hashed again
+    pub fn split_off(&mut self, at: usize) -> LinkedHashMap<K, V, S>
+    where
+        S: Clone,
+    {
+        assert!(at <= self.len(), "split position {} is out of bounds for a map of {} entries", at, self.len());
+
+        let mut tail = LinkedHashMap::with_capacity_and_hasher(self.len() - at, self.hash_builder.clone());
+        let mut iter_link = self.positions.node_at(at).unwrap_or(NIL);
+
+        while iter_link != NIL {
+            let (next, hash) = {
+                let node = node(&self.nodes, iter_link);
+                (node.next, node.hash)
+            };
+
+            let (key, val) = self.remove_node(iter_link);
+            tail.push_back(hash, key, val);
+            iter_link = next;
+        }
+
+        tail
+    }
+
+    // Splits the map right before the key, which begins the new map.
+    // None if the key is missing, leaving the map untouched
+    pub fn split_off_key<Q>(&mut self, key: &Q) -> Option<LinkedHashMap<K, V, S>>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+        S: Clone,
+    {
+        let at = self.get_index_of(key)?;
+
+        Some(self.split_off(at))
+    }
+
+    // Moves the contents out into a new map, leaving this one empty and reusable
+    pub fn take(&mut self) -> LinkedHashMap<K, V, S>
+    where
+        S: Default,
+    {
+        core::mem::take(self)
+    }
+
+    // Tallies how many values fall into each group computed from them
+    #[cfg(feature = "std")]
+    pub fn count_by<G: core::hash::Hash + Eq, F: FnMut(&V) -> G>(&self, mut key_fn: F) -> HashMap<G, usize> {
+        let mut counts = HashMap::new();
+
+        self.walk(|_, _, val| {
+            *counts.entry(key_fn(val)).or_insert(0) += 1;
+            true
+        });
+
+        counts
+    }
+
+    // Median of the values, found by sorting a copy of them so the map keeps its order.
+    // For an even number of entries, the lower of the two middle values is returned
+    pub fn median_by_value(&self) -> Option<V>
+    where
+        V: Ord + Clone,
+    {
+        let mut values = Vec::new();
+
+        self.walk(|_, _, val| {
+            values.push(val.clone());
+            true
+        });
+
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort();
+
+        Some(values.swap_remove((values.len() - 1) / 2))
+    }
+
+    // Removes and returns the oldest entry
+    pub fn pop_front(&mut self) -> Option<(K, V)> {
+        if self.first_link == NIL {
+            return None;
+        }
+
+        Some(self.remove_node(self.first_link))
+    }
+
+    // Removes and returns the newest entry
+    pub fn pop_back(&mut self) -> Option<(K, V)> {
+        if self.current_link == NIL {
+            return None;
+        }
+
+        Some(self.remove_node(self.current_link))
+    }
+
+    // Keeps only the first `len` entries, dropping the newer ones from the back.
+    // Does nothing if the map is already no longer than that
+    pub fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            self.pop_back();
+        }
+    }
+
+    // Evicts the least recently used entries, the oldest first, until at most
+    // `target` entries remain. The evicted pairs are returned in eviction order
+    pub fn shrink_to_len(&mut self, target: usize) -> Vec<(K, V)> {
+        let mut evicted = Vec::new();
+
+        while self.len() > target {
+            match self.pop_front() {
+                Some(pair) => evicted.push(pair),
+                None => break,
+            }
+        }
+
+        evicted
+    }
+
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint { insertions: self.insertions }
+    }
+
+    // Keys inserted after the checkpoint, in order of insertion.
+    // Entries removed since are not counted, whether or not they were taken before
+    pub fn keys_since(&self, cp: &Checkpoint) -> Vec<K>
+    where
+        K: Clone,
+    {
+        let mut keys = Vec::new();
+
+        self.walk(|index, key, _| {
+            if node(&self.nodes, index).insertion >= cp.insertions {
+                keys.push(K::clone(key));
+            }
+            true
+        });
+
+        keys
+    }
+
+    // Keeps only the entries the closure holds for, in one walk along the chain.
+    // The closure may also modify the values it is handed, as with HashMap::retain
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, f: F) {
+        self.retain_mut(f);
+    }
+
+    // Same as retain, named after Vec::retain_mut for callers coming from a Vec
+    pub fn retain_mut<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        let mut iter_link = self.first_link;
+
+        while iter_link != NIL {
+            let index = iter_link;
+            let node = node_mut(&mut self.nodes, index);
+            iter_link = node.next;
+
+            if !f(&node.key, &mut node.val) {
+                self.remove_node(index);
+            }
+        }
+    }
+
+    // Pops entries from the front for as long as the closure holds,
+    // returning them in order. The first failing entry stays in the map
+    pub fn drain_while<F: FnMut(&K, &V) -> bool>(&mut self, mut f: F) -> Vec<(K, V)> {
+        let mut drained = Vec::new();
+
+        while self.front_matches(&mut f) {
+            drained.extend(self.pop_front());
+        }
+
+        drained
+    }
+
+    // Reverses the chain in place by swapping the links of every node,
+    // then rebuilds the positions along the new order.
+    // The table is left alone, as no entry moves in the slab
+    pub fn reverse(&mut self) {
+        let mut iter_link = self.first_link;
+
+        while iter_link != NIL {
+            let node = node_mut(&mut self.nodes, iter_link);
+            core::mem::swap(&mut node.prev, &mut node.next);
+            iter_link = node.prev;
+        }
+
+        core::mem::swap(&mut self.first_link, &mut self.current_link);
+        self.reindex();
+    }
+
+    // Whether the order of insertion already matches ascending key order
+    pub fn is_key_sorted(&self) -> bool
+    where
+        K: Ord,
+    {
+        let mut prev_key: Option<&K> = None;
+        let mut is_sorted = true;
+
+        self.walk(|_, key, _| {
+            if let Some(prev) = prev_key {
+                if prev > key {
+                    is_sorted = false;
+                    return false;
+                }
+            }
+
+            prev_key = Some(key);
+            true
+        });
+
+        is_sorted
+    }
+
+    // Reorders the chain into ascending key order
+    pub fn sort_keys(&mut self)
+    where
+        K: Ord,
+    {
+        self.sort_by(|(a, _), (b, _)| a.cmp(b));
+    }
+
+    // Reorders the chain by the comparator. The sort is stable, so equal
+    // entries keep their relative order. Only the links are rewritten:
+    // the nodes stay in their slots, and no key or value is moved
+    pub fn sort_by<F: FnMut((&K, &V), (&K, &V)) -> core::cmp::Ordering>(&mut self, mut compare: F) {
+        let mut chain = Vec::with_capacity(self.len());
+        self.walk(|index, _, _| {
+            chain.push(index);
+            true
+        });
+
+        // The slice sort of std is a merge sort, which is what makes it stable
+        chain.sort_by(|a, b| {
+            let (a, b) = (node(&self.nodes, *a), node(&self.nodes, *b));
+            compare((&a.key, &a.val), (&b.key, &b.val))
+        });
+
+        self.relink(&chain);
+    }
+
+    // Position, key and value of the first entry whose value matches,
+    // without looking any further once it is found
+    pub fn find_with_index<F: FnMut(&V) -> bool>(&self, mut f: F) -> Option<(usize, K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let mut found = None;
+        let mut index = 0;
+
+        self.walk(|_, key, val| {
+            if f(val) {
+                found = Some((index, K::clone(key), val.clone()));
+                return false;
+            }
+
+            index += 1;
+            true
+        });
+
+        found
+    }
+
+    // Replaces the values of keys present in both maps, keeping each entry in place.
+    // Override keys missing from this map are ignored
+    #[cfg(feature = "std")]
+    pub fn apply_overrides(&mut self, overrides: &HashMap<K, V>)
+    where
+        V: Clone,
+    {
+        for (key, override_val) in overrides {
+            if let Some(val) = self.get_mut(key) {
+                *val = override_val.clone();
+            }
+        }
+    }
+
+    // Deals the entries round-robin into `n` new maps, so each shard keeps
+    // the relative order of its own entries. Panics if `n` is zero
+    pub fn shard(&self, n: usize) -> Vec<LinkedHashMap<K, V, S>>
+    where
+        K: Clone,
+        V: Clone,
+        S: Clone,
+    {
+        assert!(n > 0, "cannot shard into zero maps");
+
+        let mut shards: Vec<LinkedHashMap<K, V, S>> = (0..n)
+            .map(|_| LinkedHashMap::with_hasher(self.hasher().clone()))
+            .collect();
+        let mut index = 0;
+
+        self.walk(|_, key, val| {
+            shards[index % n].insert(K::clone(key), val.clone());
+            index += 1;
+            true
+        });
+
+        shards
+    }
+
+    // Alternates entries from both maps in their own orders, starting with this one,
+    // then appends whatever remains of the longer map.
+    // When a key appears in both, the first occurrence is kept
+    pub fn interleave(mut self, mut other: Self) -> Self
+    where
+        S: Clone,
+    {
+        let mut interleaved = LinkedHashMap::with_hasher(self.hasher().clone());
+
+        loop {
+            let self_pair = self.pop_front();
+            let other_pair = other.pop_front();
+
+            if self_pair.is_none() && other_pair.is_none() {
+                break;
+            }
+
+            for (key, val) in self_pair.into_iter().chain(other_pair) {
+                if !interleaved.contains_key(&key) {
+                    interleaved.insert(key, val);
+                }
+            }
+        }
+
+        interleaved
+    }
+
+    // How many positions the key moved going from the other map to this one,
+    // positive meaning it sits later here
+    pub fn position_shift(&self, other: &LinkedHashMap<K, V, S>, key: &K) -> Option<i64> {
+        let self_index = self.get_index_of(key)?;
+        let other_index = other.get_index_of(key)?;
+
+        Some(self_index as i64 - other_index as i64)
+    }
+
+    // Discards entries from the front for as long as the closure holds,
+    // returning how many were removed
+    pub fn pop_front_while<F: FnMut(&K, &V) -> bool>(&mut self, mut f: F) -> usize {
+        let mut popped = 0;
+
+        while self.front_matches(&mut f) {
+            self.pop_front();
+            popped += 1;
+        }
+
+        popped
+    }
+
+    pub fn as_readonly(&self) -> ReadOnlyView<'_, K, V, S> {
+        ReadOnlyView { map: self }
+    }
+
+    // Rebuilds the map with each value converted fallibly, keeping the order.
+    // Conversion stops at the first error, which is returned
+    pub fn try_map_values<W, E, F>(mut self, mut f: F) -> Result<LinkedHashMap<K, W, S>, E>
+    where
+        S: Clone,
+        F: FnMut(&K, V) -> Result<W, E>,
+    {
+        let mut mapped = LinkedHashMap::with_hasher(self.hasher().clone());
+
+        while let Some((key, val)) = self.pop_front() {
+            let mapped_val = f(&key, val)?;
+            mapped.insert(key, mapped_val);
+        }
+
+        Ok(mapped)
+    }
+
+    // Number of key pairs whose relative order differs from the target sequence,
+    // in O(n log n). The target must hold exactly the keys of the map,
+    // otherwise this panics.
+    // Each target key is replaced by its position in the map, which needs no
+    // lookup table, and the inversions of that sequence are counted while
+    // merge sorting it
+    pub fn inversions_vs(&self, target: &[K]) -> usize {
+        assert_eq!(target.len(), self.len(), "target must contain the same keys as the map");
+
+        let mut positions: Vec<usize> = target
+            .iter()
+            .map(|key| self.get_index_of(key).expect("target must contain the same keys as the map"))
+            .collect();
+
+        let inversions = count_inversions(&mut positions);
+
+        // Sorted, the positions of a target holding every key once count up from 0
+        let is_permutation = positions.iter().enumerate().all(|(i, position)| i == *position);
+        assert!(is_permutation, "target must contain the same keys as the map");
+
+        inversions
+    }
+
+    // Snapshot of the entries in order of insertion.
+    // Collecting the pairs into an IndexMap (or any other insertion ordered map)
+    // yields the same order as this map
+    pub fn to_pairs(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.iter().map(|(key, val)| (key.clone(), val.clone())).collect()
+    }
+
+    // Same snapshot as to_pairs, under the name slices and Vecs use
+    pub fn to_vec(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.to_pairs()
+    }
+
+    // Splits the positions 0..len into `buckets` even ranges and counts
+    // the matching entries that fall into each one
+    pub fn index_histogram<F: FnMut(&K, &V) -> bool>(&self, buckets: usize, mut f: F) -> Vec<usize> {
+        let mut histogram = vec![0; buckets];
+        let len = self.len();
+        let mut index = 0;
+
+        if buckets == 0 {
+            return histogram;
+        }
+
+        self.walk(|_, key, val| {
+            if f(key, val) {
+                histogram[index * buckets / len] += 1;
+            }
+
+            index += 1;
+            true
+        });
+
+        histogram
+    }
+
+    // Rebuilds the map with every key transformed, keeping the order.
+    // When several keys map to the same new key, only the first entry is kept
+    pub fn map_keys<L, F>(mut self, mut f: F) -> LinkedHashMap<L, V, S>
+    where
+        L: core::hash::Hash + Eq,
+        S: Clone,
+        F: FnMut(&K) -> L,
+    {
+        let mut mapped = LinkedHashMap::with_hasher(self.hasher().clone());
+
+        while let Some((key, val)) = self.pop_front() {
+            let mapped_key = f(&key);
+
+            if !mapped.contains_key(&mapped_key) {
+                mapped.insert(mapped_key, val);
+            }
+        }
+
+        mapped
+    }
+
+    // Up to `radius` entries on either side of the key, plus its own entry,
+    // in order of insertion and clamped at both ends of the chain
+    pub fn window_around<Q>(&self, key: &Q, radius: usize) -> Option<Vec<(K, V)>>
+    where
+        K: Clone,
+        V: Clone,
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+        let mut window_start = index;
+        let mut window_end = index;
+        let mut steps_back = 0;
+        let mut steps_forward = 0;
+
+        // Walk both ways until the radius is covered or an end is reached
+        while steps_back < radius && node(&self.nodes, window_start).prev != NIL {
+            window_start = node(&self.nodes, window_start).prev;
+            steps_back += 1;
+        }
+
+        while steps_forward < radius && node(&self.nodes, window_end).next != NIL {
+            window_end = node(&self.nodes, window_end).next;
+            steps_forward += 1;
+        }
+
+        let window = Iter {
+            nodes: &self.nodes,
+            iter_link: window_start,
+            back_link: window_end,
+            remaining: steps_back + 1 + steps_forward,
+        }
+            .map(|(key, val)| (key.clone(), val.clone()))
+            .collect();
+
+        Some(window)
+    }
+
+    // Whether both maps hold equal pairs, in any order, as std HashMaps compare
+    pub fn eq_ignore_order(&self, other: &LinkedHashMap<K, V, S>) -> bool
+    where
+        V: PartialEq,
+    {
+        self.len() == other.len() && self.iter().all(|(key, val)| other.get(key) == Some(val))
+    }
+
+    // Reference to the current state of the LinkedHashMap
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter {
+            nodes: &self.nodes,
+            iter_link: self.first_link,
+            back_link: self.current_link,
+            remaining: self.len(),
+        }
+    }
+
+    // Entries at the positions of the range, in order of insertion.
+    // Only the ends are looked up, in O(log n), and nothing is collected.
+    // Panics if the range is out of bounds, as slice indexing does
+    pub fn iter_range<R: core::ops::RangeBounds<usize>>(&self, range: R) -> Iter<'_, K, V> {
+        use core::ops::Bound;
+
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.checked_add(1).unwrap_or_else(|| {
+                panic!("range start after {} is out of bounds for a map of {} entries", start, self.len())
+            }),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.checked_add(1).unwrap_or_else(|| {
+                panic!("range end {} is out of bounds for a map of {} entries", end, self.len())
+            }),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len(),
+        };
+
+        assert!(start <= end, "range start {} is greater than its end {}", start, end);
+        assert!(end <= self.len(), "range end {} is out of bounds for a map of {} entries", end, self.len());
+
+        if start == end {
+            return Iter {
+                nodes: &self.nodes,
+                iter_link: NIL,
+                back_link: NIL,
+                remaining: 0,
+            };
+        }
+
+        Iter {
+            nodes: &self.nodes,
+            iter_link: self.positions.node_at(start).unwrap_or(NIL),
+            back_link: self.positions.node_at(end - 1).unwrap_or(NIL),
+            remaining: end - start,
+        }
+    }
+
+    // Internal iteration over every entry in order, following the links
+    // straight through the slab. Touches each node once, with no iterator
+    // state to keep between steps
+    pub fn for_each<F: FnMut(&K, &V)>(&self, mut f: F) {
+        self.walk(|_, key, val| {
+            f(key, val);
+            true
+        });
+    }
+
+    // Mutable counterpart of for_each, borrowing one node at a time
+    pub fn for_each_mut<F: FnMut(&K, &mut V)>(&mut self, mut f: F) {
+        let mut iter_link = self.first_link;
+
+        while iter_link != NIL {
+            let node = node_mut(&mut self.nodes, iter_link);
+            f(&node.key, &mut node.val);
+            iter_link = node.next;
+        }
+    }
+
+    // Mutable reference to every value, in order of insertion
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut {
+            remaining: self.table.len(),
+            iter_link: self.first_link,
+            back_link: self.current_link,
+            nodes: self.nodes.as_mut_ptr(),
+            marker: core::marker::PhantomData,
+        }
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys { inner: self.iter() }
+    }
+
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values { inner: self.iter() }
+    }
+
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut { inner: self.iter_mut() }
+    }
+
+    pub fn into_keys(self) -> IntoKeys<K, V> {
+        IntoKeys { inner: self.into_iter() }
+    }
+
+    pub fn into_values(self) -> IntoValues<K, V> {
+        IntoValues { inner: self.into_iter() }
+    }
+
+    // Walks the chain in order of insertion, borrowing each entry in place
+    // along with the index of its node.
+    // The walk stops early once the closure returns false
+    fn walk<'a, F: FnMut(NodeIndex, &'a K, &'a V) -> bool>(&'a self, mut f: F) {
+        let mut iter_link = self.first_link;
+
+        while iter_link != NIL {
+            let node = node(&self.nodes, iter_link);
+
+            if !f(iter_link, &node.key, &node.val) {
+                break;
+            }
+
+            iter_link = node.next;
+        }
+    }
+
+    fn hash_key<Q: core::hash::Hash + ?Sized>(&self, key: &Q) -> u64 {
+        self.hash_builder.hash_one(key)
+    }
+
+    // Position in the table and index in the slab of the key's node
+    fn find<Q>(&self, key: &Q) -> Option<(usize, NodeIndex)>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.find_hashed(self.hash_key(key), |stored_key| stored_key.borrow() == key)
+    }
+
+    // Relinks the key's node to the back and returns its index in the slab,
+    // for wrappers that read the node next without looking the key up again
+    fn move_to_back_index<Q>(&mut self, key: &Q) -> Option<NodeIndex>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, index) = self.find(key)?;
+
+        self.unlink(index);
+        self.link_back(index);
+
+        Some(index)
+    }
+
+    // Same as find, with the hash and the key comparison supplied by the caller
+    fn find_hashed<F: FnMut(&K) -> bool>(&self, hash: u64, mut is_match: F) -> Option<(usize, NodeIndex)> {
+        let pos = self.table.find(hash, |index| is_match(&node(&self.nodes, index).key))?;
+
+        Some((pos, self.table.index_at(pos)))
+    }
+
+    // Fills a vacant slot with a new node at the end of the chain,
+    // for a key the caller has made sure is not in the map yet
+    fn push_back(&mut self, hash: u64, key: K, val: V) -> NodeIndex {
+        let node = Node {
+            key,
+            val,
+            prev: NIL,
+            next: NIL,
+            insertion: self.insertions,
+            hash,
+        };
+
+        let index = if self.vacant != NIL {
+            let index = self.vacant;
+
+            if let Slot::Vacant(next_vacant) = self.nodes[index as usize] {
+                self.vacant = next_vacant;
+            }
+
+            self.nodes[index as usize] = Slot::Occupied(node);
+            index
+        } else {
+            // NIL itself can never be handed out as an index
+            assert!(self.nodes.len() < NIL as usize, "LinkedHashMap cannot index any more entries");
+
+            self.nodes.push(Slot::Occupied(node));
+            (self.nodes.len() - 1) as NodeIndex
+        };
+
+        self.table.insert(hash, index);
+        self.link_back(index);
+        self.insertions += 1;
+
+        index
+    }
+
+    // Removes the node found at the position in the table, freeing its slot
+    fn remove_at(&mut self, pos: usize, index: NodeIndex) -> (K, V) {
+        self.table.remove(pos);
+        self.unlink(index);
+
+        let slot = core::mem::replace(&mut self.nodes[index as usize], Slot::Vacant(self.vacant));
+        self.vacant = index;
+
+        match slot {
+            Slot::Occupied(node) => (node.key, node.val),
+            Slot::Vacant(_) => unreachable!("link to a vacant slot"),
+        }
+    }
+
+    // Removes a node known only by its index, finding its bucket by the index
+    // rather than by comparing keys. The stored hash is used rather than
+    // hashing the key again, as a raw entry may have filed it under another one
+    fn remove_node(&mut self, index: NodeIndex) -> (K, V) {
+        let hash = node(&self.nodes, index).hash;
+        let pos = self
+            .table
+            .find(hash, |bucket_index| bucket_index == index)
+            .expect("every node has a bucket in the table");
+
+        self.remove_at(pos, index)
+    }
+
+    // Detaches a node from its neighbours, moving the chain ends past it if needed.
+    // The detached node still refers to its old neighbours until it is relinked
+    fn unlink(&mut self, index: NodeIndex) {
+        let (prev, next) = {
+            let node = node(&self.nodes, index);
+            (node.prev, node.next)
+        };
+
+        // If the node is the first one, then the chain now begins one past it
+        if prev != NIL {
+            node_mut(&mut self.nodes, prev).next = next;
+        } else {
+            self.first_link = next;
+        }
+
+        // If the node is the last one, then the chain now ends one before it
+        if next != NIL {
+            node_mut(&mut self.nodes, next).prev = prev;
+        } else {
+            self.current_link = prev;
+        }
+
+        self.positions.remove(index);
+    }
+
+    // Attaches a detached node to the end of the chain, making it the current link
+    fn link_back(&mut self, index: NodeIndex) {
+        let current_link = self.current_link;
+
+        {
+            let node = node_mut(&mut self.nodes, index);
+            node.prev = current_link;
+            node.next = NIL;
+        }
+
+        if current_link != NIL {
+            node_mut(&mut self.nodes, current_link).next = index;
+        } else {
+            self.first_link = index;
+        }
+
+        self.current_link = index;
+        self.positions.push_back(index);
+    }
+
+    // Attaches a detached node to the beginning of the chain, making it the first link
+    fn link_front(&mut self, index: NodeIndex) {
+        let first_link = self.first_link;
+
+        {
+            let node = node_mut(&mut self.nodes, index);
+            node.prev = NIL;
+            node.next = first_link;
+        }
+
+        if first_link != NIL {
+            node_mut(&mut self.nodes, first_link).prev = index;
+        } else {
+            self.current_link = index;
+        }
+
+        self.first_link = index;
+        self.positions.push_front(index);
+    }
+
+    // Inserts or updates the key and detaches its node from the chain,
+    // for the caller to link somewhere else
+    fn insert_detached(&mut self, key: K, val: V) -> (NodeIndex, Option<V>) {
+        let (index, old_val) = match self.find(&key) {
+            Some((_, index)) => {
+                let old_val = core::mem::replace(&mut node_mut(&mut self.nodes, index).val, val);
+                (index, Some(old_val))
+            }
+            None => {
+                let hash = self.hash_key(&key);
+                (self.push_back(hash, key, val), None)
+            }
+        };
+
+        self.unlink(index);
+
+        (index, old_val)
+    }
+
+    // Attaches a detached node right before the anchor node
+    fn link_before(&mut self, index: NodeIndex, anchor: NodeIndex) {
+        let prev = node(&self.nodes, anchor).prev;
+
+        {
+            let node = node_mut(&mut self.nodes, index);
+            node.prev = prev;
+            node.next = anchor;
+        }
+
+        node_mut(&mut self.nodes, anchor).prev = index;
+
+        if prev != NIL {
+            node_mut(&mut self.nodes, prev).next = index;
+        } else {
+            self.first_link = index;
+        }
+
+        self.positions.insert_before(index, anchor);
+    }
+
+    // Attaches a detached node right after the anchor node
+    fn link_after(&mut self, index: NodeIndex, anchor: NodeIndex) {
+        let next = node(&self.nodes, anchor).next;
+
+        {
+            let node = node_mut(&mut self.nodes, index);
+            node.prev = anchor;
+            node.next = next;
+        }
+
+        node_mut(&mut self.nodes, anchor).next = index;
+
+        if next != NIL {
+            node_mut(&mut self.nodes, next).prev = index;
+        } else {
+            self.current_link = index;
+        }
+
+        self.positions.insert_after(index, anchor);
+    }
+
+    // Rebuilds the positions from scratch along the whole chain,
+    // for when every link has changed at once
+    fn reindex(&mut self) {
+        self.positions.clear();
+        let mut iter_link = self.first_link;
+
+        while iter_link != NIL {
+            self.positions.push_back(iter_link);
+            iter_link = node(&self.nodes, iter_link).next;
+        }
+    }
+
+    // Rebuilds the chain to follow the order of the given nodes,
+    // which must be every node of the map exactly once
+    fn relink(&mut self, order: &[NodeIndex]) {
+        self.first_link = NIL;
+        self.current_link = NIL;
+        self.positions.clear();
+
+        for index in order {
+            self.link_back(*index);
+        }
+    }
+
+    // Key and value held by a node, if there is one
+    fn pair_at(&self, index: NodeIndex) -> Option<(&K, &V)> {
+        if index == NIL {
+            return None;
+        }
+
+        let node = node(&self.nodes, index);
+
+        Some((&node.key, &node.val))
+    }
+
+    // Whether the oldest entry exists and satisfies the closure
+    fn front_matches<F: FnMut(&K, &V) -> bool>(&self, f: &mut F) -> bool {
+        match self.pair_at(self.first_link) {
+            Some((key, val)) => f(key, val),
+            None => false,
+        }
+    }
+}
+
+impl<K, V, S> Default for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn default() -> LinkedHashMap<K, V, S> {
+        LinkedHashMap::with_hasher(S::default())
+    }
+}
+
+// Collects pairs in the order they are iterated.
+// A repeated key keeps the position of its first pair and the value of its last
+impl<K, V, S> core::iter::FromIterator<(K, V)> for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> LinkedHashMap<K, V, S> {
+        let mut linked_hash_map = LinkedHashMap::with_hasher(S::default());
+        linked_hash_map.extend(iter);
+
+        linked_hash_map
+    }
+}
+
+// Builds a map from an array literal in the order of the array, as with
+// HashMap::from. Repeated keys are handled as in FromIterator
+impl<K, V, const N: usize> From<[(K, V); N]> for LinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    fn from(pairs: [(K, V); N]) -> LinkedHashMap<K, V> {
+        let mut linked_hash_map = LinkedHashMap::with_capacity(N);
+        linked_hash_map.extend(pairs);
+
+        linked_hash_map
+    }
+}
+
+// Keeps the order of the pairs. Repeated keys are handled as in FromIterator
+impl<K, V, S> From<Vec<(K, V)>> for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn from(pairs: Vec<(K, V)>) -> LinkedHashMap<K, V, S> {
+        let mut linked_hash_map = LinkedHashMap::with_capacity_and_hasher(pairs.len(), S::default());
+        linked_hash_map.extend(pairs);
+
+        linked_hash_map
+    }
+}
+
+// Moves the pairs out in order of insertion
+impl<K, V, S> From<LinkedHashMap<K, V, S>> for Vec<(K, V)>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn from(linked_hash_map: LinkedHashMap<K, V, S>) -> Vec<(K, V)> {
+        linked_hash_map.into_iter().collect()
+    }
+}
+
+// New keys are appended to the back, while existing keys are updated in place
+impl<K, V, S> Extend<(K, V)> for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.insert(key, val);
+        }
+    }
+}
+
+impl<'a, K, V, S> Extend<(&'a K, &'a V)> for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq + Copy,
+    V: Copy,
+    S: core::hash::BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&key, &val)| (key, val)));
+    }
+}
+
+impl<K, V, S> IntoIterator for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    fn into_iter(self) -> IntoIter<K, V> {
+        IntoIter {
+            remaining: self.len(),
+            iter_link: self.first_link,
+            back_link: self.current_link,
+            nodes: self.nodes,
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Iter<'a, K, V> {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for &'a mut LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    fn into_iter(self) -> IterMut<'a, K, V> {
+        self.iter_mut()
+    }
+}
+
+// The links are indices into the slab, so a copy of the slab and the table
+// links up on its own. Insertion numbers are carried over too,
+// so checkpoints of the original still apply to the copy
+impl<K, V, S> Clone for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq + Clone,
+    V: Clone,
+    S: core::hash::BuildHasher + Clone,
+{
+    fn clone(&self) -> LinkedHashMap<K, V, S> {
+        LinkedHashMap {
+            nodes: self.nodes.clone(),
+            vacant: self.vacant,
+            first_link: self.first_link,
+            current_link: self.current_link,
+            table: self.table.clone(),
+            positions: self.positions.clone(),
+            hash_builder: self.hash_builder.clone(),
+            insertions: self.insertions,
+        }
+    }
+}
+
+// Two maps are equal when they hold equal pairs in the same order,
+// like two sequences of pairs. See eq_ignore_order to disregard the order
+impl<K, V, S> PartialEq for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    V: PartialEq,
+    S: core::hash::BuildHasher,
+{
+    fn eq(&self, other: &LinkedHashMap<K, V, S>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<K, V, S> Eq for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    V: Eq,
+    S: core::hash::BuildHasher,
+{
+}
+
+// Hashes the pairs in order of insertion, agreeing with the ordered equality.
+// The length goes first, so that maps are not confused with their prefixes
+impl<K, V, S> core::hash::Hash for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    V: core::hash::Hash,
+    S: core::hash::BuildHasher,
+{
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+
+        for (key, val) in self.iter() {
+            key.hash(state);
+            val.hash(state);
+        }
+    }
+}
+
+// Indexing panics on a missing key, as with the std HashMap.
+// As with the lookup methods, any borrowed form of the key can index the map
+impl<K, Q, V, S> core::ops::Index<&Q> for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq + core::borrow::Borrow<Q>,
+    Q: core::hash::Hash + Eq + ?Sized,
+    S: core::hash::BuildHasher,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        self.get(key).expect("key not found in LinkedHashMap")
+    }
+}
+
+impl<K, Q, V, S> core::ops::IndexMut<&Q> for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq + core::borrow::Borrow<Q>,
+    Q: core::hash::Hash + Eq + ?Sized,
+    S: core::hash::BuildHasher,
+{
+    fn index_mut(&mut self, key: &Q) -> &mut V {
+        self.get_mut(key).expect("key not found in LinkedHashMap")
+    }
+}
+
+// Formats the entries as a map, in order of insertion
+impl<K, V, S> core::fmt::Debug for LinkedHashMap<K, V, S>
+where
+    K: core::hash::Hash + Eq + core::fmt::Debug,
+    V: core::fmt::Debug,
+    S: core::hash::BuildHasher,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+// Immutable borrow of a LinkedHashMap which only exposes lookups and iteration,
+// so handing one out guarantees the map is not mutated through it
+pub struct ReadOnlyView<'a, K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a LinkedHashMap<K, V, S>,
+}
+
+impl<'a, K, V, S> ReadOnlyView<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub fn get<Q>(&self, key: &Q) -> Option<&'a V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.get(key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    pub fn iter(&self) -> Iter<'a, K, V> {
+        self.map.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+// Timestamped mode, where every value is stored alongside the Instant
+// it was inserted at.
+// The clock is supplied by the caller, so timestamps are monotonically
+// increasing only as long as the caller's clock is
+#[cfg(feature = "std")]
+impl<K, V, S> LinkedHashMap<K, (Instant, V), S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    // Reinserting a key relinks it to the back along with its new timestamp,
+    // so the chain stays in timestamp order for purge_older_than
+    pub fn insert_at(&mut self, key: K, val: V, inserted_at: Instant) {
+        match self.entry(key) {
+            Entry::Occupied(mut entry) => {
+                entry.to_back();
+                entry.insert((inserted_at, val));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((inserted_at, val));
+            }
+        }
+    }
+
+    // Removes every entry inserted before the cutoff.
+    // Entries are in insertion order, so scanning stops at the first newer entry
+    pub fn purge_older_than(&mut self, cutoff: Instant) {
+        self.pop_front_while(|_, (inserted_at, _)| *inserted_at < cutoff);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::LinkedHashMap, alloc::{rc::Rc, vec, vec::Vec}};
+
+    fn keys<V>(map: &LinkedHashMap<&'static str, V>) -> Vec<&'static str> {
+        map.keys().copied().collect()
+    }
+
+
+    // Xorshift, for the randomized tests of this module and of the table and
+    // positions, which only need a repeatable stream of operations
+    pub(crate) fn next_random(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    fn from_pairs(pairs: &[(&'static str, i32)]) -> LinkedHashMap<&'static str, i32> {
+        let mut map = LinkedHashMap::new();
+
+        for &(key, val) in pairs {
+            map.insert(key, val);
+        }
+
+        map
+    }
+
+    #[test]
+    fn get_if_clones_only_passing_values() {
+        let map = from_pairs(&[("a", 1), ("b", 2)]);
+
+        assert_eq!(map.get_if("b", |val| *val > 1), Some(2));
+        assert_eq!(map.get_if("a", |val| *val > 1), None);
+        assert_eq!(map.get_if("c", |_| true), None);
+    }
+
+    #[test]
+    fn move_matching_to_back_keeps_both_orders() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5), ("f", 6)]);
+        map.move_matching_to_back(|_, val| val % 2 == 0);
+
+        assert_eq!(keys(&map), vec!["a", "c", "e", "b", "d", "f"]);
+        assert_eq!(map.get_index_of("b"), Some(3));
+    }
+
+    #[test]
+    fn scan_values_sums_cumulatively() {
+        let map = from_pairs(&[("a", 1), ("b", 2), ("c", 3)]);
+
+        assert_eq!(map.scan_values(0, |acc, val| acc + val), vec![("a", 1), ("b", 3), ("c", 6)]);
+    }
+
+    #[test]
+    fn closest_by_value_breaks_ties_by_insertion() {
+        let map = from_pairs(&[("a", 2), ("b", 8), ("c", 4), ("d", 6)]);
+
+        assert_eq!(map.closest_by_value(7, |val| *val as i64), Some("b"));
+        assert_eq!(map.closest_by_value(5, |val| *val as i64), Some("c"));
+        assert_eq!(from_pairs(&[]).closest_by_value(5, |val| *val as i64), None);
+    }
+
+    #[test]
+    fn swap_exchanges_contents() {
+        let mut first = from_pairs(&[("a", 1), ("b", 2)]);
+        let mut second = from_pairs(&[("c", 3)]);
+        first.swap(&mut second);
+
+        assert_eq!(first.to_pairs(), vec![("c", 3)]);
+        assert_eq!(second.to_pairs(), vec![("a", 1), ("b", 2)]);
+    }
+
+    #[test]
+    fn take_leaves_an_empty_reusable_map() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2)]);
+        let taken = map.take();
+
+        assert_eq!(taken.to_pairs(), vec![("a", 1), ("b", 2)]);
+        assert!(map.is_empty());
+
+        map.insert("c", 3);
+        assert_eq!(map.to_pairs(), vec![("c", 3)]);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn count_by_groups_by_parity() {
+        let map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 5)]);
+        let counts = map.count_by(|val| val % 2 == 0);
+
+        assert_eq!(counts.get(&true), Some(&1));
+        assert_eq!(counts.get(&false), Some(&3));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn purge_older_than_stops_at_the_first_newer_entry() {
+        use std::time::{Duration, Instant};
+
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let mut map = LinkedHashMap::new();
+        map.insert_at("a", 1, at(0));
+        map.insert_at("b", 2, at(1));
+        map.insert_at("c", 3, at(2));
+        map.purge_older_than(at(1));
+
+        assert_eq!(keys(&map), vec!["b", "c"]);
+
+        // A reinserted key moves behind the others along with its new timestamp,
+        // so it survives a purge of the entries that were once newer than it
+        map.insert_at("b", 4, at(3));
+        map.purge_older_than(at(3));
+
+        assert_eq!(map.to_pairs(), vec![("b", (at(3), 4))]);
+    }
+
+    #[test]
+    fn median_by_value_takes_the_lower_median() {
+        assert_eq!(from_pairs(&[("a", 3), ("b", 1), ("c", 2)]).median_by_value(), Some(2));
+        assert_eq!(from_pairs(&[("a", 4), ("b", 1), ("c", 3), ("d", 2)]).median_by_value(), Some(2));
+        assert_eq!(from_pairs(&[]).median_by_value(), None);
+    }
+
+    #[test]
+    fn shrink_to_len_evicts_the_oldest_first() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)]);
+
+        assert_eq!(map.shrink_to_len(2), vec![("a", 1), ("b", 2), ("c", 3)]);
+        assert_eq!(keys(&map), vec!["d", "e"]);
+        assert_eq!(map.shrink_to_len(2), vec![]);
+    }
+
+    #[test]
+    fn keys_since_skips_removed_entries() {
+        let mut map = from_pairs(&[("a", 1)]);
+        let cp = map.checkpoint();
+        map.insert("b", 2);
+        map.insert("c", 3);
+        map.remove("b");
+        map.remove("a");
+        map.insert("a", 4);
+
+        assert_eq!(map.keys_since(&cp), vec!["c", "a"]);
+    }
+
+    #[test]
+    fn drain_while_stops_at_the_first_failure() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2), ("c", 5), ("d", 1)]);
+
+        assert_eq!(map.drain_while(|_, val| *val < 3), vec![("a", 1), ("b", 2)]);
+        assert_eq!(keys(&map), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn is_key_sorted_follows_the_order() {
+        let mut map = from_pairs(&[("c", 3), ("a", 1), ("b", 2)]);
+        assert!(!map.is_key_sorted());
+
+        map.sort_keys();
+        assert!(map.is_key_sorted());
+
+        map.insert("a0", 0);
+        assert!(!map.is_key_sorted());
+    }
+
+    #[test]
+    fn find_with_index_returns_the_first_match() {
+        let map = from_pairs(&[("a", 1), ("b", 7), ("c", 9)]);
+
+        assert_eq!(map.find_with_index(|val| *val > 5), Some((1, "b", 7)));
+        assert_eq!(map.find_with_index(|val| *val > 9), None);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn apply_overrides_keeps_positions() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2), ("c", 3)]);
+        let overrides = [("a", 10), ("c", 30), ("z", 0)].iter().copied().collect();
+        map.apply_overrides(&overrides);
+
+        assert_eq!(map.to_pairs(), vec![("a", 10), ("b", 2), ("c", 30)]);
+    }
+
+    #[test]
+    fn shard_deals_round_robin() {
+        let map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5), ("f", 6), ("g", 7)]);
+        let shards = map.shard(3);
+
+        assert_eq!(shards.len(), 3);
+        assert_eq!(keys(&shards[0]), vec!["a", "d", "g"]);
+        assert_eq!(keys(&shards[1]), vec!["b", "e"]);
+        assert_eq!(keys(&shards[2]), vec!["c", "f"]);
+    }
+
+    #[test]
+    fn interleave_alternates_and_keeps_the_first_occurrence() {
+        let first = from_pairs(&[("a", 1), ("b", 2), ("c", 3)]);
+        let second = from_pairs(&[("x", 4), ("a", 5)]);
+
+        assert_eq!(first.interleave(second).to_pairs(), vec![("a", 1), ("x", 4), ("b", 2), ("c", 3)]);
+    }
+
+    #[test]
+    fn position_shift_counts_positions_moved() {
+        let before = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+        let after = from_pairs(&[("a", 1), ("c", 3), ("d", 4), ("b", 2)]);
+
+        assert_eq!(after.position_shift(&before, &"b"), Some(2));
+        assert_eq!(before.position_shift(&after, &"b"), Some(-2));
+        assert_eq!(after.position_shift(&before, &"z"), None);
+    }
+
+    #[test]
+    fn pop_front_while_counts_the_popped_entries() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2), ("c", 9), ("d", 1)]);
+
+        assert_eq!(map.pop_front_while(|_, val| *val < 5), 2);
+        assert_eq!(keys(&map), vec!["c", "d"]);
+    }
+
+    #[test]
+    fn readonly_view_reads_the_map() {
+        let map = from_pairs(&[("a", 1), ("b", 2)]);
+        let view = map.as_readonly();
+
+        assert_eq!(view.get("b"), Some(&2));
+        assert!(view.contains_key("a"));
+        assert!(!view.contains_key("c"));
+        assert_eq!(view.len(), 2);
+        assert!(!view.is_empty());
+        assert_eq!(view.iter().map(|(key, _)| *key).collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn try_map_values_keeps_the_order() {
+        let map = from_pairs(&[("a", 1), ("b", 2)]);
+        let mapped: Result<_, ()> = map.try_map_values(|_, val| Ok(val * 10));
+
+        assert_eq!(mapped.unwrap().to_pairs(), vec![("a", 10), ("b", 20)]);
+    }
+
+    #[test]
+    fn try_map_values_stops_at_the_first_error() {
+        let map = from_pairs(&[("a", 1), ("b", -2), ("c", -3)]);
+        let mut converted = 0;
+        let mapped = map.try_map_values(|key, val| {
+            converted += 1;
+            if val < 0 { Err(*key) } else { Ok(val) }
+        });
+
+        assert_eq!(mapped.err(), Some("b"));
+        assert_eq!(converted, 2);
+    }
+
+    #[test]
+    fn inversions_vs_counts_pairs_out_of_order() {
+        let map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4)]);
+
+        assert_eq!(map.inversions_vs(&["a", "b", "c", "d"]), 0);
+        assert_eq!(map.inversions_vs(&["d", "c", "b", "a"]), 6);
+        assert_eq!(map.inversions_vs(&["b", "a", "c", "d"]), 1);
+    }
+
+    #[test]
+    fn to_pairs_keeps_the_order() {
+        let mut map = from_pairs(&[("b", 2), ("a", 1)]);
+        map.insert("c", 3);
+        map.insert("b", 4);
+
+        assert_eq!(map.to_pairs(), vec![("b", 4), ("a", 1), ("c", 3)]);
+    }
+
+    #[test]
+    fn index_histogram_buckets_matches_by_position() {
+        let map = from_pairs(&[("a", 1), ("b", 1), ("c", 0), ("d", 0), ("e", 0), ("f", 1)]);
+
+        assert_eq!(map.index_histogram(3, |_, val| *val == 1), vec![2, 0, 1]);
+        assert_eq!(map.index_histogram(0, |_, _| true), vec![]);
+    }
+
+    #[test]
+    fn map_keys_keeps_the_first_colliding_key() {
+        let map = from_pairs(&[("aa", 1), ("b", 2), ("cc", 3)]);
+
+        assert_eq!(map.map_keys(|key| key.len()).to_pairs(), vec![(2, 1), (1, 2)]);
+    }
+
+    #[test]
+    fn window_around_clamps_at_both_ends() {
+        let map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)]);
+
+        assert_eq!(map.window_around("c", 1), Some(vec![("b", 2), ("c", 3), ("d", 4)]));
+        assert_eq!(map.window_around("b", 2), Some(vec![("a", 1), ("b", 2), ("c", 3), ("d", 4)]));
+        assert_eq!(map.window_around("e", 0), Some(vec![("e", 5)]));
+        assert_eq!(map.window_around("z", 1), None);
+    }
+
+    #[test]
+    fn removed_and_dropped_entries_are_freed() {
+        let vals: Vec<Rc<i32>> = (0..4).map(Rc::new).collect();
+        let mut map = LinkedHashMap::new();
+
+        for (key, val) in vals.iter().enumerate() {
+            map.insert(key, Rc::clone(val));
+        }
+
+        map.remove(&1);
+        assert_eq!(Rc::strong_count(&vals[1]), 1);
+
+        map.pop_front();
+        assert_eq!(Rc::strong_count(&vals[0]), 1);
+
+        drop(map);
+        assert!(vals.iter().all(|val| Rc::strong_count(val) == 1));
+    }
+
+    #[test]
+    fn capacity_counts_vacant_slots_as_room() {
+        let mut map = LinkedHashMap::with_capacity(16);
+        let capacity = map.capacity();
+        assert!(capacity >= 16);
+
+        for key in 0..16 {
+            map.insert(key, key);
+        }
+
+        for key in 0..16 {
+            map.remove(&key);
+        }
+
+        assert_eq!(map.capacity(), capacity);
+
+        for key in 16..32 {
+            map.insert(key, key);
+        }
+
+        assert_eq!(map.capacity(), capacity);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn iter_range_rejects_a_start_after_usize_max() {
+        use core::ops::Bound;
+
+        let map = from_pairs(&[("a", 1)]);
+        map.iter_range((Bound::Excluded(usize::MAX), Bound::Unbounded));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn iter_range_rejects_an_end_at_usize_max() {
+        from_pairs(&[("a", 1)]).iter_range(0..=usize::MAX);
+    }
+
+    #[test]
+    fn iter_mut_lends_each_value_once_from_both_ends() {
+        let mut map = from_pairs(&[("a", 1), ("b", 2), ("c", 3), ("d", 4), ("e", 5)]);
+        map.remove("b");
+        map.insert("f", 6);
+        map.move_to_front("e");
+
+        let mut iter = map.iter_mut();
+        let (front, back) = (iter.next().unwrap(), iter.next_back().unwrap());
+        *front.1 *= 10;
+        *back.1 *= 10;
+
+        assert_eq!((*front.0, *back.0), ("e", "f"));
+        assert_eq!(iter.len(), 3);
+        assert_eq!(iter.by_ref().map(|(key, _)| *key).collect::<Vec<_>>(), vec!["a", "c", "d"]);
+        assert!(iter.next_back().is_none());
+
+        for val in map.values_mut() {
+            *val += 1;
+        }
+
+        assert_eq!(map.to_pairs(), vec![("e", 51), ("a", 2), ("c", 4), ("d", 5), ("f", 61)]);
+    }
+
+    // Runs random edits against a Vec of the pairs in order, which every
+    // query of the map must agree with after each edit
+    #[test]
+    fn random_edits_agree_with_a_vec_model() {
+        let mut state = 0x2545_F491_4F6C_DD1D;
+        let mut map: LinkedHashMap<u32, u32> = LinkedHashMap::new();
+        let mut model: Vec<(u32, u32)> = Vec::new();
+
+        for step in 0..20_000 {
+            let key = (next_random(&mut state) % 48) as u32;
+            let val = step;
+            let model_pos = model.iter().position(|&(model_key, _)| model_key == key);
+
+            match next_random(&mut state) % 16 {
+                0..=3 => {
+                    let old_val = map.insert(key, val);
+                    assert_eq!(old_val, model_pos.map(|pos| model[pos].1));
+
+                    match model_pos {
+                        Some(pos) => model[pos].1 = val,
+                        None => model.push((key, val)),
+                    }
+                }
+                4 | 5 => {
+                    assert_eq!(map.remove(&key), model_pos.map(|pos| model.remove(pos).1));
+                }
+                6 => {
+                    assert_eq!(map.move_to_back(&key), model_pos.is_some());
+
+                    if let Some(pos) = model_pos {
+                        let pair = model.remove(pos);
+                        model.push(pair);
+                    }
+                }
+                7 => {
+                    assert_eq!(map.move_to_front(&key), model_pos.is_some());
+
+                    if let Some(pos) = model_pos {
+                        let pair = model.remove(pos);
+                        model.insert(0, pair);
+                    }
+                }
+                8 if !model.is_empty() => {
+                    let anchor = model[next_random(&mut state) as usize % model.len()].0;
+                    let after = next_random(&mut state) & 1 == 0;
+
+                    let old_val = if after {
+                        map.insert_after(&anchor, key, val)
+                    } else {
+                        map.insert_before(&anchor, key, val)
+                    };
+                    assert_eq!(old_val, model_pos.map(|pos| model[pos].1));
+
+                    if key == anchor {
+                        model[model_pos.unwrap()].1 = val;
+                    } else {
+                        if let Some(pos) = model_pos {
+                            model.remove(pos);
+                        }
+
+                        let anchor_pos = model.iter().position(|&(model_key, _)| model_key == anchor).unwrap();
+                        model.insert(anchor_pos + after as usize, (key, val));
+                    }
+                }
+                9 => {
+                    map.reverse();
+                    model.reverse();
+                }
+                10 => {
+                    map.sort_keys();
+                    model.sort();
+                }
+                11 => {
+                    let modulus = 2 + key % 5;
+                    map.retain(|key, _| key % modulus != 0);
+                    model.retain(|(key, _)| key % modulus != 0);
+                }
+                12 => {
+                    let new_key = (next_random(&mut state) % 48) as u32;
+                    let taken = model.iter().any(|&(model_key, _)| model_key == new_key && model_key != key);
+
+                    match map.replace_key(&key, new_key) {
+                        Ok(old_key) => {
+                            assert!(!taken);
+                            assert_eq!(old_key, key);
+                            model[model_pos.unwrap()].0 = new_key;
+                        }
+                        Err(returned_key) => {
+                            assert!(taken || model_pos.is_none());
+                            assert_eq!(returned_key, new_key);
+                        }
+                    }
+                }
+                13 => {
+                    // Rotates the map by moving its front half behind the rest
+                    let at = next_random(&mut state) as usize % (model.len() + 1);
+                    let mut tail = map.split_off(at);
+                    assert_eq!(tail.iter().map(|(&key, &val)| (key, val)).collect::<Vec<_>>(), model[at..]);
+
+                    tail.append(&mut map);
+                    assert!(map.is_empty());
+                    map = tail;
+                    model.rotate_left(at);
+                }
+                14 => {
+                    // Lands on the ghost position once every len + 1 steps
+                    let steps = next_random(&mut state) as usize % 8;
+                    let mut cursor = map.cursor_front_mut();
+
+                    for _ in 0..steps {
+                        cursor.move_next();
+                    }
+
+                    let pos = steps % (model.len() + 1);
+
+                    for _ in 0..2 {
+                        let removed = if pos < model.len() { Some(model.remove(pos)) } else { None };
+                        assert_eq!(cursor.remove_current(), removed);
+                    }
+                }
+                _ => map.shrink_to_fit(),
+            }
+
+            assert_eq!(map.len(), model.len());
+            assert_eq!(map.iter().map(|(&key, &val)| (key, val)).collect::<Vec<_>>(), model);
+            assert!(map.iter().rev().map(|(&key, &val)| (key, val)).eq(model.iter().rev().copied()));
+
+            for (pos, &(key, val)) in model.iter().enumerate() {
+                assert_eq!(map.get(&key), Some(&val));
+                assert_eq!(map.get_index_of(&key), Some(pos));
+                assert_eq!(map.get_index(pos), Some((&key, &val)));
+            }
+
+            assert_eq!(map.get_index(model.len()), None);
+        }
+    }
+}
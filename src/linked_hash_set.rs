@@ -0,0 +1,274 @@
+use crate::{DefaultHashBuilder, IntoKeys, Keys, LinkedHashMap};
+
+// Set of values kept in order of insertion, built on a LinkedHashMap
+// with nothing stored next to each value
+pub struct LinkedHashSet<T, S = DefaultHashBuilder>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: LinkedHashMap<T, (), S>,
+}
+
+// Iteration over the values of a LinkedHashSet, in order of insertion
+pub struct SetIter<'a, T> {
+    inner: Keys<'a, T, ()>,
+}
+
+impl<'a, T> core::iter::Iterator for SetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for SetIter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SetIter<'a, T> {}
+
+impl<'a, T> core::iter::FusedIterator for SetIter<'a, T> {}
+
+// Consuming iteration over the values of a LinkedHashSet
+pub struct SetIntoIter<T> {
+    inner: IntoKeys<T, ()>,
+}
+
+impl<T> core::iter::Iterator for SetIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<T> DoubleEndedIterator for SetIntoIter<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back()
+    }
+}
+
+impl<T> ExactSizeIterator for SetIntoIter<T> {}
+
+impl<T> core::iter::FusedIterator for SetIntoIter<T> {}
+
+impl<T> LinkedHashSet<T>
+where
+    T: core::hash::Hash + Eq,
+{
+    pub fn new() -> LinkedHashSet<T> {
+        LinkedHashSet {
+            map: LinkedHashMap::new(),
+        }
+    }
+
+    pub fn with_capacity(capacity: usize) -> LinkedHashSet<T> {
+        LinkedHashSet {
+            map: LinkedHashMap::with_capacity(capacity),
+        }
+    }
+}
+
+impl<T, S> LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> LinkedHashSet<T, S> {
+        LinkedHashSet {
+            map: LinkedHashMap::with_hasher(hash_builder),
+        }
+    }
+
+    // Whether the value was new. An existing value keeps its position
+    pub fn insert(&mut self, val: T) -> bool {
+        self.map.insert(val, ()).is_none()
+    }
+
+    pub fn contains<Q>(&self, val: &Q) -> bool
+    where
+        T: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(val)
+    }
+
+    // Whether the value was in the set
+    pub fn remove<Q>(&mut self, val: &Q) -> bool
+    where
+        T: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.remove(val).is_some()
+    }
+
+    // Removes and returns the stored value equal to the given one
+    pub fn take<Q>(&mut self, val: &Q) -> Option<T>
+    where
+        T: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (val, _) = self.map.remove_entry(val)?;
+
+        Some(val)
+    }
+
+    // Oldest value
+    pub fn front(&self) -> Option<&T> {
+        let (val, _) = self.map.front()?;
+
+        Some(val)
+    }
+
+    // Newest value
+    pub fn back(&self) -> Option<&T> {
+        let (val, _) = self.map.back()?;
+
+        Some(val)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let (val, _) = self.map.pop_front()?;
+
+        Some(val)
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let (val, _) = self.map.pop_back()?;
+
+        Some(val)
+    }
+
+    pub fn iter(&self) -> SetIter<'_, T> {
+        SetIter { inner: self.map.keys() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+}
+
+impl<T, S> Default for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn default() -> LinkedHashSet<T, S> {
+        LinkedHashSet {
+            map: LinkedHashMap::default(),
+        }
+    }
+}
+
+// Repeated values keep the position of their first occurrence
+impl<T, S> core::iter::FromIterator<T> for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> LinkedHashSet<T, S> {
+        let mut set = LinkedHashSet::default();
+        set.extend(iter);
+
+        set
+    }
+}
+
+impl<T, S> Extend<T> for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.insert(val);
+        }
+    }
+}
+
+impl<T, S> IntoIterator for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    type Item = T;
+    type IntoIter = SetIntoIter<T>;
+
+    fn into_iter(self) -> SetIntoIter<T> {
+        SetIntoIter {
+            inner: self.map.into_keys(),
+        }
+    }
+}
+
+impl<'a, T, S> IntoIterator for &'a LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    type Item = &'a T;
+    type IntoIter = SetIter<'a, T>;
+
+    fn into_iter(self) -> SetIter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<T, S> Clone for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq + Clone,
+    S: core::hash::BuildHasher + Clone,
+{
+    fn clone(&self) -> LinkedHashSet<T, S> {
+        LinkedHashSet { map: self.map.clone() }
+    }
+}
+
+// Two sets are equal when they hold equal values in the same order,
+// as with the maps
+impl<T, S> PartialEq for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn eq(&self, other: &LinkedHashSet<T, S>) -> bool {
+        self.map == other.map
+    }
+}
+
+impl<T, S> Eq for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+}
+
+impl<T, S> core::fmt::Debug for LinkedHashSet<T, S>
+where
+    T: core::hash::Hash + Eq + core::fmt::Debug,
+    S: core::hash::BuildHasher,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
@@ -0,0 +1,647 @@
+use {
+    crate::{node, node_mut, AccessOrderMap, Iter},
+    alloc::boxed::Box,
+};
+
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+// Cache holding at most a fixed number of entries, kept in order of access.
+// Putting a new key into a full cache evicts the least recently used entry.
+// With a weigher, the capacity bounds the total weight of the entries instead.
+// With std, entries can also be put with a time to live, after which
+// lookups and iteration skip them until they are removed
+pub struct LruCache<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    map: AccessOrderMap<K, CacheEntry<V>>,
+    capacity: usize,
+    accounting: Accounting<K, V>,
+}
+
+// Why the cache let go of an entry it handed to the removal listener
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemovalCause {
+    // Removed by invalidate or clear
+    Explicit,
+    // Evicted to stay within the capacity
+    Capacity,
+    // Removed after its time to live had passed
+    Expired,
+}
+
+// Closure the cache hands every entry it lets go of, for cleaning up
+// resources such as file handles. It is Send so the cache stays Send
+type RemovalListener<K, V> = Box<dyn FnMut(K, V, RemovalCause) + Send>;
+
+// Counters of how the cache has been used, for monitoring its hit rate.
+// Only get and get_mut count as lookups, while peeks do not
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    // Entries evicted to stay within the capacity
+    pub evictions: u64,
+    // Entries removed after their time to live had passed
+    pub expirations: u64,
+}
+
+impl CacheStats {
+    // Share of the lookups which were hits, or None before any lookup
+    pub fn hit_rate(&self) -> Option<f64> {
+        let lookups = self.hits + self.misses;
+
+        if lookups == 0 {
+            return None;
+        }
+
+        Some(self.hits as f64 / lookups as f64)
+    }
+
+    fn record_lookup(&mut self, hit: bool) {
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+    }
+}
+
+// Closure giving the weight of an entry, such as its size in bytes
+type Weigher<K, V> = Box<dyn Fn(&K, &V) -> usize + Send>;
+
+// Everything the cache keeps track of besides the entries themselves,
+// apart from the map so it can be updated while the map is borrowed
+struct Accounting<K, V> {
+    listener: Option<RemovalListener<K, V>>,
+    weigher: Option<Weigher<K, V>>,
+    // Sum of the weights of the entries
+    weight: usize,
+    stats: CacheStats,
+}
+
+impl<K, V> Accounting<K, V> {
+    // Every entry weighs 1 without a weigher, so the weight is the entry count
+    fn weigh(&self, key: &K, val: &V) -> usize {
+        match self.weigher {
+            Some(ref weigher) => weigher(key, val),
+            None => 1,
+        }
+    }
+
+    // Accounts for an entry leaving the cache without the listener seeing it
+    fn forget(&mut self, entry: &CacheEntry<V>) {
+        self.weight -= entry.weight;
+    }
+
+    // Accounts for an entry leaving the cache, handing it to the listener
+    fn release(&mut self, key: K, entry: CacheEntry<V>, cause: RemovalCause) {
+        self.forget(&entry);
+
+        match cause {
+            RemovalCause::Capacity => self.stats.evictions += 1,
+            RemovalCause::Expired => self.stats.expirations += 1,
+            RemovalCause::Explicit => {}
+        }
+
+        if let Some(ref mut listener) = self.listener {
+            listener(key, entry.val, cause);
+        }
+    }
+
+    // Lets go of an entry heavier than the whole capacity, which never went in
+    fn reject(&mut self, key: K, entry: CacheEntry<V>) {
+        self.weight += entry.weight;
+        self.release(key, entry, RemovalCause::Capacity);
+    }
+}
+
+// Value of a cache entry, along with its weight and when it expires, if ever
+struct CacheEntry<V> {
+    val: V,
+    weight: usize,
+    #[cfg(feature = "std")]
+    expires_at: Option<Instant>,
+}
+
+impl<V> CacheEntry<V> {
+    fn new(val: V) -> CacheEntry<V> {
+        CacheEntry {
+            val,
+            weight: 0,
+            #[cfg(feature = "std")]
+            expires_at: None,
+        }
+    }
+
+    // Without std there is no clock, and nothing ever expires
+    #[cfg(feature = "std")]
+    fn is_expired(&self, now: Now) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now.instant)
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn is_expired(&self, _now: Now) -> bool {
+        false
+    }
+}
+
+// Moment that expiry is checked against, empty without std
+#[derive(Clone, Copy)]
+struct Now {
+    #[cfg(feature = "std")]
+    instant: Instant,
+}
+
+fn now() -> Now {
+    Now {
+        #[cfg(feature = "std")]
+        instant: Instant::now(),
+    }
+}
+
+// Iteration from the least to the most recently used entry,
+// skipping the entries expired by the time it was created
+pub struct CacheIter<'a, K, V> {
+    inner: Iter<'a, K, CacheEntry<V>>,
+    now: Now,
+}
+
+impl<'a, K, V> core::iter::Iterator for CacheIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let now = self.now;
+
+        self.inner
+            .find(|(_, entry)| !entry.is_expired(now))
+            .map(|(key, entry)| (key, &entry.val))
+    }
+}
+
+impl<'a, K, V> DoubleEndedIterator for CacheIter<'a, K, V> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let now = self.now;
+
+        self.inner
+            .rfind(|(_, entry)| !entry.is_expired(now))
+            .map(|(key, entry)| (key, &entry.val))
+    }
+}
+
+impl<'a, K, V> core::iter::FusedIterator for CacheIter<'a, K, V> {}
+
+impl<K, V> LruCache<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    pub fn with_capacity(capacity: usize) -> LruCache<K, V> {
+        assert!(capacity > 0, "LruCache needs room for at least one entry");
+
+        LruCache {
+            map: AccessOrderMap::new(),
+            capacity,
+            accounting: Accounting {
+                listener: None,
+                weigher: None,
+                weight: 0,
+                stats: CacheStats::default(),
+            },
+        }
+    }
+
+    // Cache holding entries up to a total weight, as given by the weigher
+    // when each entry is put. Changing a value in place does not reweigh it.
+    // An entry heavier than the whole capacity is evicted right away,
+    // leaving the other entries in place
+    pub fn with_max_weight<F: Fn(&K, &V) -> usize + Send + 'static>(max_weight: usize, weigher: F) -> LruCache<K, V> {
+        let mut cache = LruCache::with_capacity(max_weight);
+        cache.accounting.weigher = Some(Box::new(weigher));
+
+        cache
+    }
+
+    // Replaces the removal listener. Entries the caller gets back,
+    // from put replacing a value or from remove, are not handed to it
+    pub fn set_removal_listener<F: FnMut(K, V, RemovalCause) + Send + 'static>(&mut self, listener: F) {
+        self.accounting.listener = Some(Box::new(listener));
+    }
+
+    // Returns the old value when the key was already cached.
+    // Only a new key can make room by evicting
+    pub fn put(&mut self, key: K, val: V) -> Option<V> {
+        self.put_entry(key, CacheEntry::new(val))
+    }
+
+    // Puts a new key, asking the closure which key to evict whenever the cache
+    // is full, so that other policies such as LFU or random eviction can be
+    // plugged in. The closure sees the cache as it stands, and a selector
+    // keeping to LRU returns the key of peek_lru.
+    // The first victim is returned rather than handed to the removal listener.
+    // A weighted cache may need several victims to fit the new entry, and
+    // those after the first go to the listener. A key the closure names that
+    // is not cached falls back to the least recently used entry.
+    // An existing key is only updated, as with put, and nothing is evicted
+    pub fn put_with_selector<F: FnMut(&LruCache<K, V>) -> K>(
+        &mut self,
+        key: K,
+        val: V,
+        mut select_victim: F,
+    ) -> Option<(K, V)> {
+        if self.map.contains_key(&key) {
+            self.put(key, val);
+            return None;
+        }
+
+        let mut entry = CacheEntry::new(val);
+        entry.weight = self.accounting.weigh(&key, &entry.val);
+
+        if entry.weight > self.capacity {
+            self.accounting.reject(key, entry);
+            return None;
+        }
+
+        let mut first_victim = None;
+
+        while self.accounting.weight + entry.weight > self.capacity && !self.map.is_empty() {
+            let chosen = select_victim(self);
+
+            let (victim_key, victim) = match self.map.as_map_mut().remove_entry(&chosen) {
+                Some(pair) => pair,
+                None => self.map.pop_front().expect("cache is not empty"),
+            };
+
+            if first_victim.is_none() {
+                self.accounting.forget(&victim);
+                self.accounting.stats.evictions += 1;
+                first_victim = Some((victim_key, victim.val));
+            } else {
+                self.accounting.release(victim_key, victim, RemovalCause::Capacity);
+            }
+        }
+
+        self.accounting.weight += entry.weight;
+        self.map.insert(key, entry);
+
+        first_victim
+    }
+
+    // Puts an entry which expires once the time to live has passed.
+    // A time to live reaching past what the clock can represent never expires
+    #[cfg(feature = "std")]
+    pub fn put_with_ttl(&mut self, key: K, val: V, ttl: Duration) -> Option<V> {
+        let entry = CacheEntry {
+            val,
+            weight: 0,
+            expires_at: Instant::now().checked_add(ttl),
+        };
+
+        self.put_entry(key, entry)
+    }
+
+    // An expired entry found by the lookup is removed there and then
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.remove_if_expired(key);
+
+        let entry = self.map.get(key);
+        self.accounting.stats.record_lookup(entry.is_some());
+
+        Some(&entry?.val)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.remove_if_expired(key);
+
+        let entry = self.map.get_mut(key);
+        self.accounting.stats.record_lookup(entry.is_some());
+
+        Some(&mut entry?.val)
+    }
+
+    // Value of the key, computing and putting it first on a miss.
+    // The key is hashed and looked up once: a hit is relinked through the node
+    // found, and a miss is pushed with the same hash once room is made.
+    // Room is made before the new entry goes in, so the returned borrow
+    // stays valid. None if the new entry alone outweighs the capacity,
+    // in which case it is evicted at once
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> Option<&mut V> {
+        let now = now();
+        let map = self.map.as_map_mut();
+        let hash = map.hash_key(&key);
+
+        let hit = match map.find_hashed(hash, |stored_key| *stored_key == key) {
+            Some((_, index)) if !node(&map.nodes, index).val.is_expired(now) => {
+                map.unlink(index);
+                map.link_back(index);
+                Some(index)
+            }
+            Some((pos, index)) => {
+                let (expired_key, expired) = map.remove_at(pos, index);
+                self.accounting.release(expired_key, expired, RemovalCause::Expired);
+                None
+            }
+            None => None,
+        };
+
+        self.accounting.stats.record_lookup(hit.is_some());
+
+        if let Some(index) = hit {
+            return Some(&mut node_mut(&mut self.map.as_map_mut().nodes, index).val.val);
+        }
+
+        let mut entry = CacheEntry::new(f());
+        entry.weight = self.accounting.weigh(&key, &entry.val);
+
+        if entry.weight > self.capacity {
+            self.accounting.reject(key, entry);
+            return None;
+        }
+
+        while self.accounting.weight + entry.weight > self.capacity && self.evict_front(now) {}
+
+        self.accounting.weight += entry.weight;
+
+        let map = self.map.as_map_mut();
+        let index = map.push_back(hash, key, entry);
+
+        Some(&mut node_mut(&mut map.nodes, index).val.val)
+    }
+
+    // Looks at a value without refreshing it
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let entry = self.map.peek(key)?;
+
+        if entry.is_expired(now()) {
+            return None;
+        }
+
+        Some(&entry.val)
+    }
+
+    // Modifies a value in place without refreshing it
+    pub fn peek_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let entry = self.map.peek_mut(key)?;
+
+        if entry.is_expired(now()) {
+            return None;
+        }
+
+        Some(&mut entry.val)
+    }
+
+    // Least recently used entry that has not expired.
+    // Making room lets go of entries from the front, releasing the expired
+    // ones ahead of this entry as expired, so this is the next entry to be
+    // evicted for capacity, though room may be found before reaching it
+    pub fn peek_lru(&self) -> Option<(&K, &V)> {
+        self.iter().next()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.peek(key).is_some()
+    }
+
+    // Removes the entry, handing it to the removal listener rather than
+    // returning it. Returns whether the key was cached
+    pub fn invalidate<Q>(&mut self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        match self.map.as_map_mut().remove_entry(key) {
+            Some((key, entry)) => {
+                self.accounting.release(key, entry, RemovalCause::Explicit);
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Removes every entry, handing each one to the removal listener
+    pub fn clear(&mut self) {
+        while let Some((key, entry)) = self.map.pop_front() {
+            self.accounting.release(key, entry, RemovalCause::Explicit);
+        }
+    }
+
+    // Removes the entry even if it has expired, returning its value
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let entry = self.map.remove(key)?;
+        self.accounting.forget(&entry);
+
+        Some(entry.val)
+    }
+
+    // Removes every expired entry, returning how many there were.
+    // Entries are in order of access rather than of expiry, so all are checked
+    pub fn evict_expired(&mut self) -> usize {
+        let now = now();
+        let mut cursor = self.map.as_map_mut().cursor_front_mut();
+        let mut evicted = 0;
+
+        while let Some((_, entry)) = cursor.current() {
+            if entry.is_expired(now) {
+                if let Some((key, entry)) = cursor.remove_current() {
+                    self.accounting.release(key, entry, RemovalCause::Expired);
+                }
+                evicted += 1;
+            } else {
+                cursor.move_next();
+            }
+        }
+
+        evicted
+    }
+
+    // Iterates from the least to the most recently used entry
+    pub fn iter(&self) -> CacheIter<'_, K, V> {
+        CacheIter {
+            inner: self.map.iter(),
+            now: now(),
+        }
+    }
+
+    // Number of entries held, counting expired ones not removed yet
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // Limit of the total weight, which is the entry count without a weigher
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    // Snapshot of the counters since the cache was made or the last reset
+    pub fn stats(&self) -> CacheStats {
+        self.accounting.stats
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.accounting.stats = CacheStats::default();
+    }
+
+    // Total weight of the entries held, counting expired ones not removed yet
+    pub fn weight(&self) -> usize {
+        self.accounting.weight
+    }
+
+    // An entry too heavy to ever fit is turned away before the map is touched,
+    // taking the old value under its key with it but no other entry
+    fn put_entry(&mut self, key: K, mut entry: CacheEntry<V>) -> Option<V> {
+        entry.weight = self.accounting.weigh(&key, &entry.val);
+
+        if entry.weight > self.capacity {
+            let old_entry = self.map.remove(&key);
+
+            if let Some(ref old_entry) = old_entry {
+                self.accounting.forget(old_entry);
+            }
+
+            self.accounting.reject(key, entry);
+            return Some(old_entry?.val);
+        }
+
+        self.accounting.weight += entry.weight;
+
+        let old_entry = self.map.insert(key, entry);
+
+        if let Some(ref old_entry) = old_entry {
+            self.accounting.forget(old_entry);
+        }
+
+        let now = now();
+
+        while self.accounting.weight > self.capacity && self.evict_front(now) {}
+
+        Some(old_entry?.val)
+    }
+
+    // Makes room by letting go of the least recently used entry. One whose
+    // time to live has passed is released as expired rather than as evicted.
+    // Returns false if there was no entry left to let go of
+    fn evict_front(&mut self, now: Now) -> bool {
+        match self.map.pop_front() {
+            Some((key, entry)) => {
+                let cause = if entry.is_expired(now) {
+                    RemovalCause::Expired
+                } else {
+                    RemovalCause::Capacity
+                };
+
+                self.accounting.release(key, entry, cause);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove_if_expired<Q>(&mut self, key: &Q)
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        if !self.map.peek(key).is_some_and(|entry| entry.is_expired(now())) {
+            return;
+        }
+
+        if let Some((key, entry)) = self.map.as_map_mut().remove_entry(key) {
+            self.accounting.release(key, entry, RemovalCause::Expired);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::LruCache, alloc::vec::Vec};
+
+    #[test]
+    fn put_with_selector_evicts_the_newest() {
+        let mut cache = LruCache::with_capacity(3);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        cache.put("c", 3);
+
+        let newest = |cache: &LruCache<&'static str, i32>| *cache.iter().next_back().unwrap().0;
+
+        assert_eq!(cache.put_with_selector("d", 4, newest), Some(("c", 3)));
+        assert_eq!(cache.put_with_selector("e", 5, newest), Some(("d", 4)));
+
+        let entries: Vec<_> = cache.iter().map(|(key, val)| (*key, *val)).collect();
+        assert_eq!(entries, [("a", 1), ("b", 2), ("e", 5)]);
+        assert_eq!(cache.stats().evictions, 2);
+    }
+
+    #[test]
+    fn put_with_selector_only_evicts_when_full() {
+        let mut cache = LruCache::with_capacity(2);
+        let oldest = |cache: &LruCache<&'static str, i32>| *cache.peek_lru().unwrap().0;
+
+        assert_eq!(cache.put_with_selector("a", 1, oldest), None);
+        assert_eq!(cache.put_with_selector("b", 2, oldest), None);
+        assert_eq!(cache.put_with_selector("a", 10, oldest), None);
+        assert_eq!(cache.put_with_selector("c", 3, oldest), Some(("b", 2)));
+        assert_eq!(cache.peek(&"a"), Some(&10));
+    }
+
+    #[test]
+    fn put_with_selector_falls_back_to_lru() {
+        let mut cache = LruCache::with_capacity(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+
+        assert_eq!(cache.put_with_selector("c", 3, |_| "missing"), Some(("a", 1)));
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn overweight_entries_leave_the_rest_in_place() {
+        let mut cache = LruCache::with_max_weight(10, |_, val: &usize| *val);
+        cache.put("a", 3);
+        cache.put("b", 3);
+        cache.put("c", 3);
+
+        assert_eq!(cache.put("huge", 50), None);
+        assert_eq!(cache.get_or_insert_with("huge", || 50), None);
+        assert_eq!(cache.put_with_selector("huge", 50, |_| "a"), None);
+        assert_eq!(cache.len(), 3);
+        assert_eq!(cache.weight(), 9);
+        assert_eq!(cache.stats().evictions, 3);
+
+        // Raising the weight of a cached key past the capacity only drops that key
+        assert_eq!(cache.put("a", 20), Some(3));
+
+        let keys: Vec<_> = cache.iter().map(|(key, _)| *key).collect();
+        assert_eq!(keys, ["b", "c"]);
+        assert_eq!(cache.weight(), 6);
+        assert_eq!(cache.stats().evictions, 4);
+    }
+}
@@ -0,0 +1,21 @@
+// Builds a LinkedHashMap with the entries in the order they are written,
+// as the maplit macros do for the std maps:
+// linked_hash_map! { "a" => 1, "b" => 2 }
+// A repeated key keeps its first position and takes its last value, as with insert
+#[macro_export]
+macro_rules! linked_hash_map {
+    // Stands in for a key when counting the entries, without evaluating it
+    (@unit $key:expr) => {
+        ()
+    };
+    () => {
+        $crate::LinkedHashMap::new()
+    };
+    ($($key:expr => $val:expr),+ $(,)?) => {{
+        let mut linked_hash_map = $crate::LinkedHashMap::with_capacity([$($crate::linked_hash_map!(@unit $key)),+].len());
+        $(
+            linked_hash_map.insert($key, $val);
+        )+
+        linked_hash_map
+    }};
+}
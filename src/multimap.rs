@@ -0,0 +1,216 @@
+use {
+    crate::{DefaultHashBuilder, Iter, Keys, LinkedHashMap},
+    alloc::{vec, vec::Vec},
+};
+
+// Map holding any number of values per key, such as HTTP headers
+// or query parameters. Keys stay in order of their first insertion,
+// and the values of each key in the order they were inserted
+pub struct LinkedHashMultimap<K, V, S = DefaultHashBuilder>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: LinkedHashMap<K, Vec<V>, S>,
+    // Values across every key
+    len: usize,
+}
+
+// Iteration over every key and value pair, with each key repeated
+// for every one of its values
+pub struct MultiIter<'a, K, V> {
+    keys: Iter<'a, K, Vec<V>>,
+    current: Option<(&'a K, core::slice::Iter<'a, V>)>,
+    remaining: usize,
+}
+
+impl<'a, K, V> core::iter::Iterator for MultiIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((key, ref mut vals)) = self.current {
+                if let Some(val) = vals.next() {
+                    self.remaining -= 1;
+                    return Some((key, val));
+                }
+            }
+
+            let (key, vals) = self.keys.next()?;
+            self.current = Some((key, vals.iter()));
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for MultiIter<'a, K, V> {}
+
+impl<'a, K, V> core::iter::FusedIterator for MultiIter<'a, K, V> {}
+
+impl<K, V> LinkedHashMultimap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    pub fn new() -> LinkedHashMultimap<K, V> {
+        LinkedHashMultimap::with_hasher(DefaultHashBuilder::default())
+    }
+}
+
+impl<K, V, S> LinkedHashMultimap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub fn with_hasher(hash_builder: S) -> LinkedHashMultimap<K, V, S> {
+        LinkedHashMultimap {
+            map: LinkedHashMap::with_hasher(hash_builder),
+            len: 0,
+        }
+    }
+
+    // Appends the value after the other values of the key.
+    // A new key goes to the end, while an existing one keeps its position
+    pub fn insert(&mut self, key: K, val: V) {
+        self.map.entry(key).or_default().push(val);
+        self.len += 1;
+    }
+
+    // First value of the key
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.get(key)?.first()
+    }
+
+    // Every value of the key, empty for a missing key
+    pub fn get_all<Q>(&self, key: &Q) -> &[V]
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        match self.map.get(key) {
+            Some(vals) => vals,
+            None => &[],
+        }
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.map.contains_key(key)
+    }
+
+    // Removes the key with all of its values
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<Vec<V>>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let vals = self.map.remove(key)?;
+        self.len -= vals.len();
+
+        Some(vals)
+    }
+
+    // Replaces all values of the key with a single one, keeping its position.
+    // Returns the values it had
+    pub fn replace(&mut self, key: K, val: V) -> Option<Vec<V>> {
+        let old_vals = self.map.insert(key, vec![val]);
+        self.len += 1;
+
+        if let Some(ref old_vals) = old_vals {
+            self.len -= old_vals.len();
+        }
+
+        old_vals
+    }
+
+    pub fn iter(&self) -> MultiIter<'_, K, V> {
+        MultiIter {
+            keys: self.map.iter(),
+            current: None,
+            remaining: self.len,
+        }
+    }
+
+    // Every key with all of its values
+    pub fn iter_all(&self) -> Iter<'_, K, Vec<V>> {
+        self.map.iter()
+    }
+
+    pub fn keys(&self) -> Keys<'_, K, Vec<V>> {
+        self.map.keys()
+    }
+
+    // Number of values across every key
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn keys_len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.len = 0;
+    }
+}
+
+impl<K, V, S> Default for LinkedHashMultimap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn default() -> LinkedHashMultimap<K, V, S> {
+        LinkedHashMultimap::with_hasher(S::default())
+    }
+}
+
+impl<K, V, S> core::iter::FromIterator<(K, V)> for LinkedHashMultimap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher + Default,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> LinkedHashMultimap<K, V, S> {
+        let mut multimap = LinkedHashMultimap::default();
+        multimap.extend(iter);
+
+        multimap
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for LinkedHashMultimap<K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, val) in iter {
+            self.insert(key, val);
+        }
+    }
+}
+
+// Formats each key with the list of its values
+impl<K, V, S> core::fmt::Debug for LinkedHashMultimap<K, V, S>
+where
+    K: core::hash::Hash + Eq + core::fmt::Debug,
+    V: core::fmt::Debug,
+    S: core::hash::BuildHasher,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.map.fmt(f)
+    }
+}
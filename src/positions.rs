@@ -0,0 +1,369 @@
+use alloc::vec::Vec;
+
+// Stands in for a missing child or parent, as NIL does for links
+const NONE: u32 = u32::MAX;
+
+// Place of one node in the tree, kept at the slab index of the node
+#[derive(Clone, Copy)]
+struct TreeNode {
+    left: u32,
+    right: u32,
+    parent: u32,
+    // Number of nodes in the subtree rooted here
+    size: u32,
+    priority: u32,
+}
+
+const EMPTY_TREE_NODE: TreeNode = TreeNode {
+    left: NONE,
+    right: NONE,
+    parent: NONE,
+    size: 0,
+    priority: 0,
+};
+
+// Positions of the nodes in the chain, for looking entries up by position.
+// The linked nodes form an implicit treap in the order of the chain:
+// a binary tree whose in-order walk follows the chain, kept balanced by
+// random priorities, with the size of every subtree stored at its root.
+// The position of a node is then the number of nodes left of it in the
+// walk, counted by climbing to the root, and linking or unlinking a node
+// anywhere in the chain only touches one path, all in O(log n) expected
+#[derive(Clone)]
+pub(crate) struct Positions {
+    // Indexed by slab index, like the slab itself
+    tree: Vec<TreeNode>,
+    root: u32,
+    // State of the generator handing out priorities
+    seed: u64,
+}
+
+impl Positions {
+    pub(crate) fn new() -> Positions {
+        Positions {
+            tree: Vec::new(),
+            root: NONE,
+            seed: 0,
+        }
+    }
+
+    pub(crate) fn push_back(&mut self, index: u32) {
+        self.push_end(index, true);
+    }
+
+    pub(crate) fn push_front(&mut self, index: u32) {
+        self.push_end(index, false);
+    }
+
+    // The anchor must already be linked, and the index not yet
+    pub(crate) fn insert_before(&mut self, index: u32, anchor: u32) {
+        self.insert_at(self.position(anchor), index);
+    }
+
+    pub(crate) fn insert_after(&mut self, index: u32, anchor: u32) {
+        self.insert_at(self.position(anchor) + 1, index);
+    }
+
+    // Replaces the node by the merge of its subtrees, then shrinks the
+    // subtrees on the path above it
+    pub(crate) fn remove(&mut self, index: u32) {
+        let TreeNode { left, right, parent, .. } = self.tree[index as usize];
+        self.set_parent(left, NONE);
+        self.set_parent(right, NONE);
+
+        let merged = self.merge(left, right);
+        self.set_parent(merged, parent);
+
+        if parent == NONE {
+            self.root = merged;
+        } else if self.tree[parent as usize].left == index {
+            self.tree[parent as usize].left = merged;
+        } else {
+            self.tree[parent as usize].right = merged;
+        }
+
+        self.tree[index as usize] = EMPTY_TREE_NODE;
+
+        let mut ancestor = parent;
+
+        while ancestor != NONE {
+            self.tree[ancestor as usize].size -= 1;
+            ancestor = self.tree[ancestor as usize].parent;
+        }
+    }
+
+    // Position in the chain of the linked node
+    pub(crate) fn position(&self, index: u32) -> usize {
+        let mut position = self.size(self.tree[index as usize].left);
+        let mut child = index;
+        let mut parent = self.tree[index as usize].parent;
+
+        while parent != NONE {
+            if self.tree[parent as usize].right == child {
+                position += self.size(self.tree[parent as usize].left) + 1;
+            }
+
+            child = parent;
+            parent = self.tree[parent as usize].parent;
+        }
+
+        position as usize
+    }
+
+    // Node at the position in the chain, found by descending the tree
+    pub(crate) fn node_at(&self, position: usize) -> Option<u32> {
+        if position >= self.size(self.root) as usize {
+            return None;
+        }
+
+        let mut remaining = position as u32;
+        let mut subtree = self.root;
+
+        loop {
+            let left_size = self.size(self.tree[subtree as usize].left);
+
+            if remaining < left_size {
+                subtree = self.tree[subtree as usize].left;
+            } else if remaining == left_size {
+                return Some(subtree);
+            } else {
+                remaining -= left_size + 1;
+                subtree = self.tree[subtree as usize].right;
+            }
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.tree.clear();
+        self.root = NONE;
+    }
+
+    // Pushing at either end only has to walk down that side of the tree,
+    // to where the new node outranks the rest of the side. That rest then
+    // hangs off the inner side of the new node, in one pass and without splitting
+    fn push_end(&mut self, index: u32, back: bool) {
+        let priority = self.make_node(index);
+        let mut parent = NONE;
+        let mut subtree = self.root;
+
+        while subtree != NONE && self.tree[subtree as usize].priority > priority {
+            self.tree[subtree as usize].size += 1;
+            parent = subtree;
+            subtree = self.outer(subtree, back);
+        }
+
+        if back {
+            self.tree[index as usize].left = subtree;
+        } else {
+            self.tree[index as usize].right = subtree;
+        }
+
+        self.update(index);
+        self.tree[index as usize].parent = parent;
+
+        if parent == NONE {
+            self.root = index;
+        } else if back {
+            self.tree[parent as usize].right = index;
+        } else {
+            self.tree[parent as usize].left = index;
+        }
+    }
+
+    fn insert_at(&mut self, position: usize, index: u32) {
+        self.make_node(index);
+
+        let (before, after) = self.split(self.root, position as u32);
+        let front = self.merge(before, index);
+        self.root = self.merge(front, after);
+        self.set_parent(self.root, NONE);
+    }
+
+    // Sets up an unlinked node of the tree, returning its fresh priority
+    fn make_node(&mut self, index: u32) -> u32 {
+        if index as usize >= self.tree.len() {
+            self.tree.resize(index as usize + 1, EMPTY_TREE_NODE);
+        }
+
+        let priority = self.next_priority();
+        self.tree[index as usize] = TreeNode {
+            size: 1,
+            priority,
+            ..EMPTY_TREE_NODE
+        };
+
+        priority
+    }
+
+    // Child on the side of the chain end being pushed at
+    fn outer(&self, subtree: u32, back: bool) -> u32 {
+        if back {
+            self.tree[subtree as usize].right
+        } else {
+            self.tree[subtree as usize].left
+        }
+    }
+
+    // Splits the subtree into its first `count` nodes and the rest.
+    // The roots of both halves may keep stale parents, which whoever takes
+    // them in next overwrites
+    fn split(&mut self, subtree: u32, count: u32) -> (u32, u32) {
+        if subtree == NONE {
+            return (NONE, NONE);
+        }
+
+        let TreeNode { left, right, .. } = self.tree[subtree as usize];
+        let left_size = self.size(left);
+
+        if count <= left_size {
+            let (before, after) = self.split(left, count);
+            self.tree[subtree as usize].left = after;
+            self.update(subtree);
+
+            (before, subtree)
+        } else {
+            let (before, after) = self.split(right, count - left_size - 1);
+            self.tree[subtree as usize].right = before;
+            self.update(subtree);
+
+            (subtree, after)
+        }
+    }
+
+    // Joins two subtrees, every node of the first coming before the second
+    fn merge(&mut self, first: u32, second: u32) -> u32 {
+        if first == NONE {
+            return second;
+        }
+
+        if second == NONE {
+            return first;
+        }
+
+        if self.tree[first as usize].priority > self.tree[second as usize].priority {
+            let right = self.tree[first as usize].right;
+            self.tree[first as usize].right = self.merge(right, second);
+            self.update(first);
+
+            first
+        } else {
+            let left = self.tree[second as usize].left;
+            self.tree[second as usize].left = self.merge(first, left);
+            self.update(second);
+
+            second
+        }
+    }
+
+    // Recounts the subtree and claims its children
+    fn update(&mut self, subtree: u32) {
+        let TreeNode { left, right, .. } = self.tree[subtree as usize];
+        self.tree[subtree as usize].size = 1 + self.size(left) + self.size(right);
+        self.set_parent(left, subtree);
+        self.set_parent(right, subtree);
+    }
+
+    fn size(&self, subtree: u32) -> u32 {
+        if subtree == NONE {
+            0
+        } else {
+            self.tree[subtree as usize].size
+        }
+    }
+
+    fn set_parent(&mut self, subtree: u32, parent: u32) {
+        if subtree != NONE {
+            self.tree[subtree as usize].parent = parent;
+        }
+    }
+
+    // SplitMix64, which is plenty for balancing: the priorities only have to
+    // be independent of the order the nodes are linked in
+    fn next_priority(&mut self) -> u32 {
+        self.seed = self.seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+
+        let mut z = self.seed;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+
+        (z ^ (z >> 31)) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::Positions, crate::tests::next_random, alloc::vec::Vec};
+
+    fn assert_matches(positions: &Positions, model: &[u32]) {
+        for (pos, &index) in model.iter().enumerate() {
+            assert_eq!(positions.position(index), pos);
+            assert_eq!(positions.node_at(pos), Some(index));
+        }
+
+        assert_eq!(positions.node_at(model.len()), None);
+    }
+
+    #[test]
+    fn pushing_at_both_ends_keeps_the_order() {
+        let mut positions = Positions::new();
+
+        for index in 0..100 {
+            if index % 3 == 0 {
+                positions.push_front(index);
+            } else {
+                positions.push_back(index);
+            }
+        }
+
+        let mut model: Vec<u32> = (0..100).filter(|index| index % 3 == 0).rev().collect();
+        model.extend((0..100).filter(|index| index % 3 != 0));
+        assert_matches(&positions, &model);
+    }
+
+    // The model is the chain of slab indices in order, as the map links them
+    #[test]
+    fn random_edits_agree_with_a_vec_model() {
+        let mut state = 0xD1B5_4A32_D192_ED03;
+        let mut positions = Positions::new();
+        let mut model: Vec<u32> = Vec::new();
+
+        for _ in 0..20_000 {
+            let index = (next_random(&mut state) % 96) as u32;
+            let model_pos = model.iter().position(|&model_index| model_index == index);
+
+            match (next_random(&mut state) % 8, model_pos) {
+                (0, None) => {
+                    positions.push_back(index);
+                    model.push(index);
+                }
+                (1, None) => {
+                    positions.push_front(index);
+                    model.insert(0, index);
+                }
+                (2 | 3, None) if !model.is_empty() => {
+                    let anchor_pos = next_random(&mut state) as usize % model.len();
+                    let anchor = model[anchor_pos];
+
+                    if next_random(&mut state) & 1 == 0 {
+                        positions.insert_before(index, anchor);
+                        model.insert(anchor_pos, index);
+                    } else {
+                        positions.insert_after(index, anchor);
+                        model.insert(anchor_pos + 1, index);
+                    }
+                }
+                (0..=6, Some(model_pos)) => {
+                    positions.remove(index);
+                    model.remove(model_pos);
+                }
+                (7, _) if next_random(&mut state) & 63 == 0 => {
+                    positions.clear();
+                    model.clear();
+                }
+                _ => {}
+            }
+
+            assert_matches(&positions, &model);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::{node, node_mut, LinkedHashMap, NodeIndex};
+
+// Raw entries look keys up by a hash the caller has already computed,
+// along with a closure telling the matching key apart from the others.
+// The hash has to be the one the map's hasher gives the matching key,
+// as with `map.hasher().hash_one(key)`. Any other hash simply finds nothing,
+// and an entry inserted under any other hash can only be found through
+// raw entries using that same hash, though it is still iterated and removed
+pub struct RawEntryBuilder<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a LinkedHashMap<K, V, S>,
+}
+
+pub struct RawEntryBuilderMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+}
+
+pub enum RawEntryMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    Occupied(RawOccupiedEntryMut<'a, K, V, S>),
+    Vacant(RawVacantEntryMut<'a, K, V, S>),
+}
+
+pub struct RawOccupiedEntryMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+    pos: usize,
+    index: NodeIndex,
+}
+
+pub struct RawVacantEntryMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    map: &'a mut LinkedHashMap<K, V, S>,
+}
+
+impl<'a, K, V, S> RawEntryBuilder<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub(crate) fn new(map: &'a LinkedHashMap<K, V, S>) -> RawEntryBuilder<'a, K, V, S> {
+        RawEntryBuilder { map }
+    }
+
+    pub fn from_hash<F: FnMut(&K) -> bool>(self, hash: u64, is_match: F) -> Option<(&'a K, &'a V)> {
+        let (_, index) = self.map.find_hashed(hash, is_match)?;
+
+        self.map.pair_at(index)
+    }
+}
+
+impl<'a, K, V, S> RawEntryBuilderMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub(crate) fn new(map: &'a mut LinkedHashMap<K, V, S>) -> RawEntryBuilderMut<'a, K, V, S> {
+        RawEntryBuilderMut { map }
+    }
+
+    pub fn from_hash<F: FnMut(&K) -> bool>(self, hash: u64, is_match: F) -> RawEntryMut<'a, K, V, S> {
+        match self.map.find_hashed(hash, is_match) {
+            Some((pos, index)) => RawEntryMut::Occupied(RawOccupiedEntryMut { map: self.map, pos, index }),
+            None => RawEntryMut::Vacant(RawVacantEntryMut { map: self.map }),
+        }
+    }
+}
+
+impl<'a, K, V, S> RawEntryMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    // A vacant entry builds its key and value only when it is inserted,
+    // and hashes the key with the map's hasher
+    pub fn or_insert_with<F: FnOnce() -> (K, V)>(self, default: F) -> (&'a K, &'a mut V) {
+        match self {
+            RawEntryMut::Occupied(entry) => entry.into_key_value(),
+            RawEntryMut::Vacant(entry) => {
+                let (key, val) = default();
+                entry.insert(key, val)
+            }
+        }
+    }
+}
+
+impl<'a, K, V, S> RawOccupiedEntryMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    pub fn key(&self) -> &K {
+        &node(&self.map.nodes, self.index).key
+    }
+
+    pub fn get(&self) -> &V {
+        &node(&self.map.nodes, self.index).val
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut node_mut(&mut self.map.nodes, self.index).val
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        &mut node_mut(&mut self.map.nodes, self.index).val
+    }
+
+    pub fn into_key_value(self) -> (&'a K, &'a mut V) {
+        let node = node_mut(&mut self.map.nodes, self.index);
+
+        (&node.key, &mut node.val)
+    }
+
+    // Replaces the value, keeping the entry in its position
+    pub fn insert(&mut self, val: V) -> V {
+        core::mem::replace(self.get_mut(), val)
+    }
+
+    pub fn remove(self) -> V {
+        let (_, val) = self.remove_entry();
+
+        val
+    }
+
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.remove_at(self.pos, self.index)
+    }
+}
+
+impl<'a, K, V, S> RawVacantEntryMut<'a, K, V, S>
+where
+    K: core::hash::Hash + Eq,
+    S: core::hash::BuildHasher,
+{
+    // Inserts at the end of the chain, hashing the key with the map's hasher
+    pub fn insert(self, key: K, val: V) -> (&'a K, &'a mut V) {
+        let hash = self.map.hash_key(&key);
+
+        self.insert_hashed_nocheck(hash, key, val)
+    }
+
+    // Inserts at the end of the chain under the given hash, which is not checked
+    // against the key. The key must not already be in the map
+    pub fn insert_hashed_nocheck(self, hash: u64, key: K, val: V) -> (&'a K, &'a mut V) {
+        let index = self.map.push_back(hash, key, val);
+        let node = node_mut(&mut self.map.nodes, index);
+
+        (&node.key, &mut node.val)
+    }
+}
@@ -0,0 +1,151 @@
+use {
+    crate::LinkedHashMap,
+    std::{
+        collections::hash_map::RandomState,
+        hash::BuildHasher,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex, MutexGuard, PoisonError,
+        },
+    },
+};
+
+// Concurrent map split into a fixed number of shards, each a LinkedHashMap
+// behind its own lock. Keys are routed to a shard by their hash,
+// so threads working on different shards never wait for each other.
+// Every new key is stamped from one counter shared by all shards,
+// which keeps an approximate global order of insertion for eviction
+pub struct ShardedLinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    shards: Vec<Mutex<LinkedHashMap<K, (u64, V)>>>,
+    // Routes keys independently of the hashers inside the shards
+    hash_builder: RandomState,
+    stamps: AtomicU64,
+}
+
+impl<K, V> ShardedLinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    // Panics if `shards` is zero
+    pub fn with_shards(shards: usize) -> ShardedLinkedHashMap<K, V> {
+        assert!(shards > 0, "ShardedLinkedHashMap needs at least one shard");
+
+        ShardedLinkedHashMap {
+            shards: (0..shards).map(|_| Mutex::new(LinkedHashMap::new())).collect(),
+            hash_builder: RandomState::new(),
+            stamps: AtomicU64::new(0),
+        }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+
+    // An existing key keeps its place, and only its value is replaced
+    pub fn insert(&self, key: K, val: V) -> Option<V> {
+        let mut shard = self.shard_for(&key);
+
+        if let Some((_, old_val)) = shard.get_mut(&key) {
+            return Some(core::mem::replace(old_val, val));
+        }
+
+        let stamp = self.stamps.fetch_add(1, Ordering::Relaxed);
+        shard.insert(key, (stamp, val));
+
+        None
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        V: Clone,
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, val) = self.shard_for(key).get(key)?.clone();
+
+        Some(val)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.shard_for(key).contains_key(key)
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        let (_, val) = self.shard_for(key).remove(key)?;
+
+        Some(val)
+    }
+
+    // Removes the oldest entry across all shards.
+    // The shards are locked one at a time, so entries inserted meanwhile by
+    // other threads may be missed, and the order is only approximate
+    pub fn pop_front(&self) -> Option<(K, V)> {
+        let mut oldest: Option<(usize, u64)> = None;
+
+        for (i, shard) in self.shards.iter().enumerate() {
+            if let Some((_, &(stamp, _))) = lock(shard).front() {
+                match oldest {
+                    Some((_, oldest_stamp)) if oldest_stamp < stamp => {}
+                    _ => oldest = Some((i, stamp)),
+                }
+            }
+        }
+
+        let (i, _) = oldest?;
+        let (key, (_, val)) = lock(&self.shards[i]).pop_front()?;
+
+        Some((key, val))
+    }
+
+    // Sum of the shard lengths, which may be out of date by the time it returns
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| lock(shard).len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| lock(shard).is_empty())
+    }
+
+    // Copy of every pair, ordered by the stamps of the shards
+    pub fn snapshot(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        let mut stamped = Vec::new();
+
+        for shard in self.shards.iter() {
+            for (key, (stamp, val)) in lock(shard).iter() {
+                stamped.push((*stamp, key.clone(), val.clone()));
+            }
+        }
+
+        stamped.sort_by_key(|(stamp, _, _)| *stamp);
+        stamped.into_iter().map(|(_, key, val)| (key, val)).collect()
+    }
+
+    // A borrowed form of a key hashes the same as the key itself,
+    // so both are routed to the same shard
+    fn shard_for<Q: core::hash::Hash + ?Sized>(&self, key: &Q) -> MutexGuard<'_, LinkedHashMap<K, (u64, V)>> {
+        let hash = self.hash_builder.hash_one(key);
+
+        lock(&self.shards[hash as usize % self.shards.len()])
+    }
+}
+
+// A shard whose lock was poisoned was still only changed through its safe methods,
+// so it is fine to keep using
+fn lock<T>(shard: &Mutex<T>) -> MutexGuard<'_, T> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
@@ -0,0 +1,112 @@
+use {
+    crate::LinkedHashMap,
+    std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+// LinkedHashMap behind a RwLock, for sharing one map between threads
+// through an Arc. Every method locks for the duration of the call only,
+// so values are handed out as clones rather than as borrows into the map.
+// Lookups take the lock for reading, so they can run side by side
+pub struct SyncLinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    map: RwLock<LinkedHashMap<K, V>>,
+}
+
+impl<K, V> SyncLinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    pub fn new() -> SyncLinkedHashMap<K, V> {
+        SyncLinkedHashMap {
+            map: RwLock::new(LinkedHashMap::new()),
+        }
+    }
+
+    pub fn insert(&self, key: K, val: V) -> Option<V> {
+        self.write_lock().insert(key, val)
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<V>
+    where
+        V: Clone,
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.read_lock().get(key).cloned()
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.read_lock().contains_key(key)
+    }
+
+    pub fn remove<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: core::borrow::Borrow<Q>,
+        Q: core::hash::Hash + Eq + ?Sized,
+    {
+        self.write_lock().remove(key)
+    }
+
+    pub fn pop_front(&self) -> Option<(K, V)> {
+        self.write_lock().pop_front()
+    }
+
+    pub fn len(&self) -> usize {
+        self.read_lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read_lock().is_empty()
+    }
+
+    // Copy of every pair in order of insertion, taken under a single lock,
+    // so it is consistent even while other threads keep writing
+    pub fn snapshot(&self) -> Vec<(K, V)>
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.read_lock().to_pairs()
+    }
+
+    // Runs the closure against the map under a read lock,
+    // for lookups the wrapper does not provide
+    pub fn read<R, F: FnOnce(&LinkedHashMap<K, V>) -> R>(&self, f: F) -> R {
+        f(&self.read_lock())
+    }
+
+    // Runs the closure against the map under a write lock,
+    // for changes that have to happen together
+    pub fn write<R, F: FnOnce(&mut LinkedHashMap<K, V>) -> R>(&self, f: F) -> R {
+        f(&mut self.write_lock())
+    }
+
+    pub fn into_inner(self) -> LinkedHashMap<K, V> {
+        self.map.into_inner().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // A thread panicking while holding the lock cannot leave the map half updated
+    // through its safe methods, so a poisoned lock is still fine to use
+    fn read_lock(&self) -> RwLockReadGuard<'_, LinkedHashMap<K, V>> {
+        self.map.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_lock(&self) -> RwLockWriteGuard<'_, LinkedHashMap<K, V>> {
+        self.map.write().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<K, V> Default for SyncLinkedHashMap<K, V>
+where
+    K: core::hash::Hash + Eq,
+{
+    fn default() -> SyncLinkedHashMap<K, V> {
+        SyncLinkedHashMap::new()
+    }
+}
@@ -0,0 +1,271 @@
+use alloc::{collections::TryReserveError, vec, vec::Vec};
+
+// Marks a bucket holding no index
+const EMPTY: u32 = u32::MAX;
+
+#[derive(Clone, Copy)]
+struct Bucket {
+    hash: u64,
+    index: u32,
+}
+
+const EMPTY_BUCKET: Bucket = Bucket { hash: 0, index: EMPTY };
+
+// Open addressing table from the hash of each key to the index of its node,
+// probed linearly from the bucket the mixed hash points at.
+// The table never sees the keys themselves: the caller compares the key of
+// each candidate node, so every key is only stored once, in its node.
+// At most 7/8 of the buckets are filled, so every probe reaches an empty one
+#[derive(Clone)]
+pub(crate) struct IndexTable {
+    // Always empty or a power of two long, so a hash is masked into a position
+    buckets: Vec<Bucket>,
+    len: usize,
+}
+
+impl IndexTable {
+    pub(crate) fn with_capacity(capacity: usize) -> IndexTable {
+        let mut table = IndexTable {
+            buckets: Vec::new(),
+            len: 0,
+        };
+        table.reserve(capacity);
+
+        table
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    // Number of indices the table holds before it has to grow
+    pub(crate) fn capacity(&self) -> usize {
+        self.buckets.len() / 8 * 7
+    }
+
+    // Position of the bucket whose index satisfies the closure,
+    // among the buckets holding the same hash
+    pub(crate) fn find<F: FnMut(u32) -> bool>(&self, hash: u64, mut eq: F) -> Option<usize> {
+        if self.buckets.is_empty() {
+            return None;
+        }
+
+        let mask = self.buckets.len() - 1;
+        let mut pos = self.ideal(hash);
+
+        loop {
+            let bucket = self.buckets[pos];
+
+            if bucket.index == EMPTY {
+                return None;
+            }
+
+            if bucket.hash == hash && eq(bucket.index) {
+                return Some(pos);
+            }
+
+            pos = (pos + 1) & mask;
+        }
+    }
+
+    pub(crate) fn index_at(&self, pos: usize) -> u32 {
+        self.buckets[pos].index
+    }
+
+    // The index must not already be in the table
+    pub(crate) fn insert(&mut self, hash: u64, index: u32) {
+        self.reserve(1);
+        self.place(Bucket { hash, index });
+        self.len += 1;
+    }
+
+    // Empties the bucket, then shifts later buckets of the same probe run back
+    // into the gap, so no probe is ever cut short by it
+    pub(crate) fn remove(&mut self, pos: usize) {
+        let mask = self.buckets.len() - 1;
+        let mut hole = pos;
+        let mut next = (hole + 1) & mask;
+
+        while self.buckets[next].index != EMPTY {
+            let ideal = self.ideal(self.buckets[next].hash);
+
+            // The bucket may only move back if the hole lies between its ideal
+            // position and where it sits now
+            if next.wrapping_sub(ideal) & mask >= next.wrapping_sub(hole) & mask {
+                self.buckets[hole] = self.buckets[next];
+                hole = next;
+            }
+
+            next = (next + 1) & mask;
+        }
+
+        self.buckets[hole] = EMPTY_BUCKET;
+        self.len -= 1;
+    }
+
+    pub(crate) fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            *bucket = EMPTY_BUCKET;
+        }
+
+        self.len = 0;
+    }
+
+    pub(crate) fn reserve(&mut self, additional: usize) {
+        let needed = self.len.checked_add(additional).expect("capacity overflow");
+
+        if needed > self.capacity() {
+            self.resize(bucket_count(needed).expect("capacity overflow"));
+        }
+    }
+
+    pub(crate) fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let needed = self.len.saturating_add(additional);
+
+        if needed <= self.capacity() {
+            return Ok(());
+        }
+
+        // An impossible size still has to be reported through a TryReserveError,
+        // which only a failing allocation can produce
+        let count = bucket_count(needed).unwrap_or(usize::MAX);
+        let mut buckets = Vec::new();
+        buckets.try_reserve_exact(count)?;
+        buckets.resize(count, EMPTY_BUCKET);
+
+        self.rehash_into(buckets);
+        Ok(())
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        let count = bucket_count(self.len).unwrap_or(self.buckets.len());
+
+        if count < self.buckets.len() {
+            self.resize(count);
+        }
+    }
+
+    fn resize(&mut self, count: usize) {
+        self.rehash_into(vec![EMPTY_BUCKET; count]);
+    }
+
+    fn rehash_into(&mut self, buckets: Vec<Bucket>) {
+        let old_buckets = core::mem::replace(&mut self.buckets, buckets);
+
+        for bucket in old_buckets {
+            if bucket.index != EMPTY {
+                self.place(bucket);
+            }
+        }
+    }
+
+    // Position a hash points at, taken from the top bits of the hash times an
+    // odd constant (Fibonacci hashing). Every bit of the hash takes part, so
+    // hashes differing only in their high bits, as FxHash gives for aligned
+    // addresses and shifted IDs, still spread over the table instead of
+    // piling into one probe run. The buckets keep the full hash
+    fn ideal(&self, hash: u64) -> usize {
+        let shift = 64 - self.buckets.len().trailing_zeros();
+
+        (hash.wrapping_mul(0x9E37_79B9_7F4A_7C15) >> shift) as usize
+    }
+
+    // Puts the bucket into the first empty position of its probe run
+    fn place(&mut self, bucket: Bucket) {
+        let mask = self.buckets.len() - 1;
+        let mut pos = self.ideal(bucket.hash);
+
+        while self.buckets[pos].index != EMPTY {
+            pos = (pos + 1) & mask;
+        }
+
+        self.buckets[pos] = bucket;
+    }
+}
+
+// Smallest power of two number of buckets holding `capacity` indices
+fn bucket_count(capacity: usize) -> Option<usize> {
+    if capacity == 0 {
+        return Some(0);
+    }
+
+    let mut count: usize = 8;
+
+    while count / 8 * 7 < capacity {
+        count = count.checked_mul(2)?;
+    }
+
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::IndexTable, crate::tests::next_random, alloc::vec::Vec};
+
+    // Hashes of a few distinct values, so that probe runs share buckets and
+    // different indices share a hash, which removal has to shift past
+    fn hash_of(index: u32) -> u64 {
+        (index % 7) as u64 * 0x1000_0000_0000
+    }
+
+    fn find(table: &IndexTable, index: u32) -> Option<usize> {
+        table.find(hash_of(index), |found| found == index)
+    }
+
+    #[test]
+    fn removal_keeps_colliding_indices_reachable() {
+        let mut table = IndexTable::with_capacity(0);
+
+        for index in 0..20 {
+            table.insert(hash_of(index), index);
+        }
+
+        for index in (0..20).step_by(3) {
+            let pos = find(&table, index).unwrap();
+            assert_eq!(table.index_at(pos), index);
+            table.remove(pos);
+        }
+
+        for index in 0..20 {
+            assert_eq!(find(&table, index).is_some(), index % 3 != 0);
+        }
+
+        assert_eq!(table.len(), 13);
+    }
+
+    #[test]
+    fn random_edits_agree_with_a_vec_model() {
+        let mut state = 0x9E37_79B9_7F4A_7C15;
+        let mut table = IndexTable::with_capacity(0);
+        let mut model: Vec<u32> = Vec::new();
+
+        for _ in 0..20_000 {
+            let index = (next_random(&mut state) % 64) as u32;
+            let model_pos = model.iter().position(|&model_index| model_index == index);
+
+            match (next_random(&mut state) % 8, model_pos) {
+                (0..=3, None) => {
+                    table.insert(hash_of(index), index);
+                    model.push(index);
+                }
+                (0..=5, Some(model_pos)) => {
+                    table.remove(find(&table, index).unwrap());
+                    model.swap_remove(model_pos);
+                }
+                (6, _) => table.shrink_to_fit(),
+                (7, _) if next_random(&mut state) & 63 == 0 => {
+                    table.clear();
+                    model.clear();
+                }
+                _ => {}
+            }
+
+            assert_eq!(table.len(), model.len());
+            assert!(table.capacity() >= table.len());
+
+            for index in 0..64 {
+                assert_eq!(find(&table, index).map(|pos| table.index_at(pos)), model.contains(&index).then_some(index));
+            }
+        }
+    }
+}